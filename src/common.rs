@@ -2,72 +2,302 @@ extern crate num;
 
 use self::num::traits::Num;
 
-pub trait Number: Num + Clone + Copy {
+pub trait Number: Num + Clone + Copy + PartialOrd {
     fn powf(&self, pow: f64) -> f64;
+    fn to_f64(&self) -> f64;
+}
+
+/// Fixed-width little-endian encoding of a number, used by `Vector::to_bytes` and
+/// `Matrix::to_bytes`.
+pub trait LeBytes: Number {
+    /// Number of bytes used by `write_le` and `read_le`.
+    fn byte_width() -> usize;
+    fn write_le(&self, bytes: &mut Vec<u8>);
+    fn read_le(bytes: &[u8]) -> Self;
 }
 
 impl Number for f64 {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self
+    }
 }
 
 impl Number for f32 {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
 }
 
 impl Number for i64 {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
 }
 
 impl Number for i32 {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
 }
 
 impl Number for i16 {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
 }
 
 impl Number for i8 {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
 }
 
 impl Number for u64 {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
 }
 
 impl Number for u32 {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
 }
 
 impl Number for u16 {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
 }
 
 impl Number for u8 {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
 }
 
 impl Number for usize {
     fn powf(&self, pow: f64) -> f64 {
         (*self as f64).powf(pow)
     }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+}
+
+impl LeBytes for f64 {
+    fn byte_width() -> usize {
+        8
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> f64 {
+        let mut b = [0; 8];
+        b.copy_from_slice(&bytes[.. 8]);
+        f64::from_le_bytes(b)
+    }
+}
+
+impl LeBytes for f32 {
+    fn byte_width() -> usize {
+        4
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> f32 {
+        let mut b = [0; 4];
+        b.copy_from_slice(&bytes[.. 4]);
+        f32::from_le_bytes(b)
+    }
+}
+
+impl LeBytes for i64 {
+    fn byte_width() -> usize {
+        8
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> i64 {
+        let mut b = [0; 8];
+        b.copy_from_slice(&bytes[.. 8]);
+        i64::from_le_bytes(b)
+    }
+}
+
+impl LeBytes for i32 {
+    fn byte_width() -> usize {
+        4
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> i32 {
+        let mut b = [0; 4];
+        b.copy_from_slice(&bytes[.. 4]);
+        i32::from_le_bytes(b)
+    }
+}
+
+impl LeBytes for i16 {
+    fn byte_width() -> usize {
+        2
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> i16 {
+        let mut b = [0; 2];
+        b.copy_from_slice(&bytes[.. 2]);
+        i16::from_le_bytes(b)
+    }
+}
+
+impl LeBytes for i8 {
+    fn byte_width() -> usize {
+        1
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> i8 {
+        let mut b = [0; 1];
+        b.copy_from_slice(&bytes[.. 1]);
+        i8::from_le_bytes(b)
+    }
+}
+
+impl LeBytes for u64 {
+    fn byte_width() -> usize {
+        8
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> u64 {
+        let mut b = [0; 8];
+        b.copy_from_slice(&bytes[.. 8]);
+        u64::from_le_bytes(b)
+    }
+}
+
+impl LeBytes for u32 {
+    fn byte_width() -> usize {
+        4
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> u32 {
+        let mut b = [0; 4];
+        b.copy_from_slice(&bytes[.. 4]);
+        u32::from_le_bytes(b)
+    }
+}
+
+impl LeBytes for u16 {
+    fn byte_width() -> usize {
+        2
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> u16 {
+        let mut b = [0; 2];
+        b.copy_from_slice(&bytes[.. 2]);
+        u16::from_le_bytes(b)
+    }
+}
+
+impl LeBytes for u8 {
+    fn byte_width() -> usize {
+        1
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> u8 {
+        let mut b = [0; 1];
+        b.copy_from_slice(&bytes[.. 1]);
+        u8::from_le_bytes(b)
+    }
+}
+
+impl LeBytes for usize {
+    fn byte_width() -> usize {
+        8
+    }
+
+    fn write_le(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&(*self as u64).to_le_bytes())
+    }
+
+    fn read_le(bytes: &[u8]) -> usize {
+        let mut b = [0; 8];
+        b.copy_from_slice(&bytes[.. 8]);
+        u64::from_le_bytes(b) as usize
+    }
 }
@@ -0,0 +1,57 @@
+//! Errors returned by the non-panicking counterparts of the vector and matrix operations.
+
+use std::error::Error;
+use std::fmt;
+
+use common::Number;
+use matrix::Matrix;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    /// The number of given elements doesn't match the requested length.
+    LengthMismatch { expected: usize, found: usize },
+    /// The (rows, cols) dimensions of two operands don't match.
+    DimensionMismatch { expected: (usize, usize), found: (usize, usize) },
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ShapeError::LengthMismatch { expected, found } => {
+                write!(f, "Expected {} elements, found {}!", expected, found)
+            }
+            ShapeError::DimensionMismatch { expected, found } => {
+                write!(f, "Expected dimensions {:?}, found {:?}!", expected, found)
+            }
+        }
+    }
+}
+
+impl Error for ShapeError {}
+
+/// Checks that two matrices have the same dimensions, as required by the element-wise
+/// operations.
+///
+/// ```
+/// use lars::error::{self, ShapeError};
+/// use lars::matrix;
+///
+/// let a = matrix::from(2, 3, &[1, 2, 3, 4, 5, 6]);
+/// let b = matrix::from(3, 2, &[1, 2, 3, 4, 5, 6]);
+///
+/// assert_eq!(error::check_same_shape(&a, &a), Ok(()));
+/// assert_eq!(error::check_same_shape(&a, &b), Err(ShapeError::DimensionMismatch {
+///     expected: (2, 3),
+///     found: (3, 2),
+/// }));
+/// ```
+pub fn check_same_shape<T: Number>(a: &Matrix<T>, b: &Matrix<T>) -> Result<(), ShapeError> {
+    if a.same_shape(b) {
+        Ok(())
+    } else {
+        Err(ShapeError::DimensionMismatch {
+            expected: a.shape(),
+            found: b.shape(),
+        })
+    }
+}
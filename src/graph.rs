@@ -0,0 +1,60 @@
+//! Graph algorithms on adjacency matrices.
+
+use matrix::Matrix;
+use vector::Vector;
+
+/// PageRank of every node by power iteration, where `adjacency.get(i, j)` is the
+/// weight of the edge from `i` to `j`. Every step keeps `damping` of the rank flowing
+/// along the out-edges and spreads the rest uniformly; nodes without out-edges spread
+/// all their rank uniformly. The ranks sum to one.
+///
+/// ```
+/// use lars::graph;
+/// use lars::matrix;
+///
+/// // 0 → 1, 0 → 2, 1 → 2, 2 → 0
+/// let adjacency = matrix::from(3, 3, &[0.0, 1.0, 1.0,
+///                                      0.0, 0.0, 1.0,
+///                                      1.0, 0.0, 0.0]);
+/// let rank = graph::pagerank(&adjacency, 0.85, 100);
+///
+/// assert!((rank[0] - 0.387789).abs() < 1e-5);
+/// assert!((rank[1] - 0.214810).abs() < 1e-5);
+/// assert!((rank[2] - 0.397400).abs() < 1e-5);
+/// ```
+pub fn pagerank(adjacency: &Matrix<f64>, damping: f64, iterations: usize) -> Vector<f64> {
+    let size = adjacency.get_rows();
+    if adjacency.get_cols() != size {
+        panic!("Matrix must be a square!")
+    }
+
+    let mut out = vec![0.0; size];
+    for (n, total) in out.iter_mut().enumerate() {
+        for m in 0 .. size {
+            *total += adjacency.get(n, m);
+        }
+    }
+
+    let mut rank = Vector::new(size, 1.0 / size as f64);
+    for _ in 0 .. iterations {
+        let mut dangling = 0.0;
+        for n in 0 .. size {
+            if out[n] == 0.0 {
+                dangling += rank[n];
+            }
+        }
+
+        let base = (1.0 - damping + damping * dangling) / size as f64;
+        let mut next = Vector::new(size, base);
+        for n in 0 .. size {
+            if out[n] == 0.0 {
+                continue
+            }
+            for m in 0 .. size {
+                next[m] += damping * rank[n] * adjacency.get(n, m) / out[n];
+            }
+        }
+        rank = next;
+    }
+    rank
+}
@@ -1,3 +1,10 @@
 pub mod common;
+pub mod error;
 pub mod vector;
 pub mod matrix;
+pub mod numeric;
+pub mod optimize;
+pub mod regression;
+pub mod nn;
+pub mod stats;
+pub mod graph;
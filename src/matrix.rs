@@ -95,10 +95,16 @@
 
 extern crate rand;
 
+use self::rand::Rng;
+
 use std::ops::{Index, Add, Sub, Mul, Div, Neg};
+use std::cell::Cell;
+use std::convert::TryFrom;
 use std::fmt;
 
-use common::Number;
+use common::{LeBytes, Number};
+use error;
+use error::ShapeError;
 use vector;
 use vector::Vector;
 
@@ -108,18 +114,43 @@ pub struct Matrix<T: Number> {
     content: Vector<T>,
 }
 
+/// Output size of `Matrix::convolve2d`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvMode {
+    /// Every position where the kernel overlaps the matrix, `(r + kr - 1, c + kc - 1)`.
+    Full,
+    /// Same shape as the matrix, centered on the full output.
+    Same,
+    /// Only positions where the kernel fits entirely inside the matrix.
+    Valid,
+}
+
+/// Element-wise activation functions for `Matrix::apply_activation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activation {
+    /// `1 / (1 + e⁻ˣ)`
+    Sigmoid,
+    /// `max(0, x)`
+    Relu,
+    /// `tanh(x)`
+    Tanh,
+    /// `eˣⁱ / Σⱼ eˣʲ` over every row, so each row sums to one.
+    Softmax,
+    /// `x`, unchanged.
+    Identity,
+}
+
+/// Prints the matrix as a grid, right-aligning every column to its widest element.
+///
+/// ```
+/// use lars::matrix;
+///
+/// let m = matrix::from(2, 2, &[1, 10, 100, 2]);
+/// assert_eq!(format!("{}", m), "[[  1 10]\n [100  2]]");
+/// ```
 impl<T: Number + fmt::Display> fmt::Display for Matrix<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        try!(write!(f, "["));
-        for n in 0 .. self.rows {
-            try!(write!(f, "["));
-            for m in 0 .. self.cols {
-                try!(write!(f, "{}", self.get(n, m)));
-            }
-            try!(write!(f, "],\n"));
-        }
-        try!(write!(f, "]"));
-        Ok(())
+        f.write_str(&self.format_grid(|x| format!("{}", x)))
     }
 }
 
@@ -128,7 +159,7 @@ impl<T: Number> Index<usize> for Matrix<T> {
 
     #[inline]
     fn index<'a>(&'a self, index: usize) -> &'a [T] {
-        &self.content.content[self.rows * index .. self.rows * index + self.cols]
+        &self.content.as_slice()[self.rows * index .. self.rows * index + self.cols]
     }
 }
 
@@ -148,35 +179,53 @@ impl<T: Number> Clone for Matrix<T> {
     }
 }
 
+/// Builds a `rows` by `cols` matrix from a flat slice, failing instead of
+/// panicking when the slice doesn't hold exactly `rows * cols` elements.
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use lars::error::ShapeError;
+/// use lars::matrix::Matrix;
+///
+/// let elements = [1.0, 3.0, 3.0, 7.0];
+///
+/// let m = Matrix::try_from((2, 2, &elements[..])).unwrap();
+/// assert_eq!(m.get(1, 0), 3.0);
+///
+/// let err = Matrix::try_from((2, 3, &elements[..])).err();
+/// assert_eq!(err, Some(ShapeError::LengthMismatch { expected: 6, found: 4 }));
+///
+/// // A shape whose element count overflows reports `usize::MAX` as expected.
+/// let err = Matrix::<f64>::try_from((usize::MAX, 2, &[][..])).err();
+/// assert_eq!(err, Some(ShapeError::LengthMismatch { expected: usize::MAX, found: 0 }));
+/// ```
+impl<'a, T: Number> TryFrom<(usize, usize, &'a [T])> for Matrix<T> {
+    type Error = ShapeError;
+
+    fn try_from(shape: (usize, usize, &'a [T])) -> Result<Matrix<T>, ShapeError> {
+        let (rows, cols, elements) = shape;
+        match rows.checked_mul(cols) {
+            Some(len) if len == elements.len() => Ok(from(rows, cols, elements)),
+            expected => Err(ShapeError::LengthMismatch {
+                expected: expected.unwrap_or(usize::MAX),
+                found: elements.len(),
+            }),
+        }
+    }
+}
+
 impl<T: Number> Add<Matrix<T>> for Matrix<T> {
     type Output = Matrix<T>;
     fn add(self, rhs: Matrix<T>) -> Matrix<T> {
-        if self.rows == rhs.rows
-                && self.cols == rhs.cols {
-            Matrix::<T> {
-                rows: self.rows,
-                cols: self.cols,
-                content: self.content + rhs.content,
-            }
-        } else {
-            panic!("Can't add matrices of different dimensions!");
-        }
+        self.add_ref(&rhs)
     }
 }
 
 impl<T: Number> Sub<Matrix<T>> for Matrix<T> {
     type Output = Matrix<T>;
     fn sub(self, rhs: Matrix<T>) -> Matrix<T> {
-        if self.rows == rhs.rows
-                && self.cols == rhs.cols {
-            Matrix::<T> {
-                rows: self.rows,
-                cols: self.cols,
-                content: self.content - rhs.content,
-            }
-        } else {
-            panic!("Can't subtract matrices of different dimensions!");
-        }
+        self.sub_ref(&rhs)
     }
 }
 
@@ -184,21 +233,7 @@ impl<T: Number> Mul<Matrix<T>> for Matrix<T> {
     type Output = Matrix<T>;
 
     fn mul(self, rhs: Matrix<T>) -> Matrix<T> {
-        if self.cols == rhs.cols {
-            let mut pass = Matrix::<T>::new(self.rows, self.cols, T::zero());
-            for n in 0 .. self.rows {
-                for m in 0 .. rhs.cols {
-                    let mut product: T = T::zero();
-                    for k in 0 .. self.cols {
-                        product = product + self.get(n, k) * rhs.get(k, m);
-                    }
-                    pass.set(n, m, product);
-                }
-            }
-            pass
-        } else {
-            panic!("Can't multiply matrices of different dimensions!")
-        }
+        self.mul_ref(&rhs)
     }
 }
 
@@ -206,21 +241,7 @@ impl<T: Number> Div<Matrix<T>> for Matrix<T> {
     type Output = Matrix<T>;
 
     fn div(self, rhs: Matrix<T>) -> Matrix<T> {
-        if self.cols == rhs.cols {
-            let mut pass = Matrix::<T>::new(self.rows, self.cols, T::zero());
-            for n in 0 .. self.rows {
-                for m in 0 .. rhs.cols {
-                    let mut product: T = T::zero();
-                    for k in 0 .. self.cols {
-                        product = product + self.get(n, k) / rhs.get(k, m);
-                    }
-                    pass.set(n, m, product);
-                }
-            }
-            pass
-        } else {
-            panic!("Can't divide matrices of different dimensions!")
-        }
+        self.div_ref(&rhs)
     }
 }
 
@@ -252,21 +273,7 @@ impl<T: Number> Mul<Vector<T>> for Matrix<T> {
     type Output = Vector<T>;
 
     fn mul(self, rhs: Vector<T>) -> Vector<T> {
-        if self.cols == rhs.content.len() {
-            let mut pass = Vector::<T>::new(0, T::zero());
-            let mut i = 0;
-            while i < self.content.len() / self.cols {
-                let mut p = T::zero();
-                for n in 0 .. self.cols {
-                    p = p + self.content[(i * self.cols) + n] * rhs.content[n];
-                }
-                pass.content.push(p);
-                i += 1
-            }
-            pass
-        } else {
-            panic!("Can't multiply matrix with given vector!")
-        }
+        &self * &rhs
     }
 }
 
@@ -274,15 +281,15 @@ impl<T: Number> Add<Vector<T>> for Matrix<T> {
     type Output = Vector<T>;
 
     fn add(self, rhs: Vector<T>) -> Vector<T> {
-        if self.cols == rhs.content.len() {
+        if self.cols == rhs.len() {
             let mut pass = Vector::<T>::new(0, T::zero());
             let mut i = 0;
             while i < self.content.len() / self.cols {
                 let mut p = T::zero();
                 for n in 0 .. self.cols {
-                    p = p + self.content[(i * self.cols) + n] + rhs.content[n];
+                    p = p + self.content[(i * self.cols) + n] + rhs[n];
                 }
-                pass.content.push(p);
+                pass.push(p);
                 i += 1
             }
             pass
@@ -296,15 +303,15 @@ impl<T: Number> Sub<Vector<T>> for Matrix<T> {
     type Output = Vector<T>;
 
     fn sub(self, rhs: Vector<T>) -> Vector<T> {
-        if self.cols == rhs.content.len() {
+        if self.cols == rhs.len() {
             let mut pass = Vector::<T>::new(0, T::zero());
             let mut i = 0;
             while i < self.content.len() / self.cols {
                 let mut p = T::zero();
                 for n in 0 .. self.cols {
-                    p = p + self.content[(i * self.cols) + n] - rhs.content[n];
+                    p = p + self.content[(i * self.cols) + n] - rhs[n];
                 }
-                pass.content.push(p);
+                pass.push(p);
                 i += 1
             }
             pass
@@ -318,15 +325,15 @@ impl<T: Number> Div<Vector<T>> for Matrix<T> {
     type Output = Vector<T>;
 
     fn div(self, rhs: Vector<T>) -> Vector<T> {
-        if self.cols == rhs.content.len() {
+        if self.cols == rhs.len() {
             let mut pass = Vector::<T>::new(0, T::zero());
             let mut i = 0;
             while i < self.content.len() / self.cols {
                 let mut p = T::zero();
                 for n in 0 .. self.cols {
-                    p = p + self.content[(i * self.cols) + n] / rhs.content[n];
+                    p = p + self.content[(i * self.cols) + n] / rhs[n];
                 }
-                pass.content.push(p);
+                pass.push(p);
                 i += 1
             }
             pass
@@ -372,13 +379,142 @@ impl<T: Number> Sub<T> for Matrix<T> {
     }
 }
 
+/// Borrowing counterpart of `Add<Matrix<T>>`, leaving both operands usable.
+///
+/// ```
+/// use lars::matrix;
+///
+/// let a = matrix::from(2, 2, &[1.0, 2.0, 3.0, 4.0]);
+/// let b = matrix::from(2, 2, &[5.0, 6.0, 7.0, 8.0]);
+///
+/// assert!(&a + &b == a.clone() + b.clone());
+/// assert!(&a - &b == a.clone() - b.clone());
+/// assert!(&a * &b == a.clone() * b.clone());
+/// assert!(&a / &b == a.clone() / b.clone());
+/// assert!(&a * 2.0 == a.clone() * 2.0);
+///
+/// // Neither a nor b were moved
+/// assert_eq!(a.get_rows() + b.get_rows(), 4);
+/// ```
+impl<T: Number> Add<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: &Matrix<T>) -> Matrix<T> {
+        self.add_ref(rhs)
+    }
+}
+
+impl<T: Number> Sub<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, rhs: &Matrix<T>) -> Matrix<T> {
+        self.sub_ref(rhs)
+    }
+}
+
+impl<T: Number> Mul<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: &Matrix<T>) -> Matrix<T> {
+        self.mul_ref(rhs)
+    }
+}
+
+/// Matrix-vector product without consuming (or cloning) either operand.
+///
+/// ```
+/// use lars::matrix;
+/// use lars::vector;
+///
+/// let m = matrix::from(2, 3, &[1, 2, 3, 4, 5, 6]);
+/// let v = vector::from(&[1, 0, -1]);
+///
+/// assert!(&m * &v == vector::from(&[-2, -2]));
+/// assert!(&m * &v == m * v);
+/// ```
+impl<T: Number> Mul<&Vector<T>> for &Matrix<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, rhs: &Vector<T>) -> Vector<T> {
+        if self.cols == rhs.len() {
+            let mut pass = Vector::<T>::new(0, T::zero());
+            let mut i = 0;
+            while i < self.content.len() / self.cols {
+                let mut p = T::zero();
+                for n in 0 .. self.cols {
+                    p = p + self.content[(i * self.cols) + n] * rhs[n];
+                }
+                pass.push(p);
+                i += 1
+            }
+            pass
+        } else {
+            panic!("Can't multiply matrix with given vector!")
+        }
+    }
+}
+
+impl<T: Number> Div<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn div(self, rhs: &Matrix<T>) -> Matrix<T> {
+        self.div_ref(rhs)
+    }
+}
+
+impl<T: Number> Add<T> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: T) -> Matrix<T> {
+        Matrix::<T> {
+            rows: self.rows,
+            cols: self.cols,
+            content: &self.content + rhs,
+        }
+    }
+}
+
+impl<T: Number> Sub<T> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, rhs: T) -> Matrix<T> {
+        Matrix::<T> {
+            rows: self.rows,
+            cols: self.cols,
+            content: &self.content - rhs,
+        }
+    }
+}
+
+impl<T: Number> Mul<T> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: T) -> Matrix<T> {
+        Matrix::<T> {
+            rows: self.rows,
+            cols: self.cols,
+            content: &self.content * rhs,
+        }
+    }
+}
+
+impl<T: Number> Div<T> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn div(self, rhs: T) -> Matrix<T> {
+        Matrix::<T> {
+            rows: self.rows,
+            cols: self.cols,
+            content: &self.content / rhs,
+        }
+    }
+}
+
+/// Same shape and element-wise `==`, see the `PartialEq` of `Vector` for how floats
+/// (NaN in particular) compare and `total_eq` for the alternative.
 impl<T: Number> PartialEq for Matrix<T> {
     fn eq(&self, other: &Matrix<T>) -> bool {
-        if self.rows != other.rows
-                || self.cols != other.cols {
-            return false;
-        }
-        self.content == other.content
+        self.same_shape(other) && self.content == other.content
     }
 }
 
@@ -420,8 +556,115 @@ impl<T: Number> Matrix<T> {
         }
     }
 
+    /// Reshapes like `reshape` when the element counts match, reinterpreting the
+    /// row-major elements. Otherwise resizes instead: elements keep their
+    /// `(row, col)` position, those outside the new shape are dropped and new
+    /// positions are filled with `fill`. Panics if `rows * cols` overflows.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let mut m = matrix::from(2, 3, &[1, 2, 3, 4, 5, 6]);
+    /// m.reshape_or_resize(3, 2, 0);
+    /// assert!(m == matrix::from(3, 2, &[1, 2, 3, 4, 5, 6]));
+    ///
+    /// let mut m = matrix::from(2, 2, &[1, 2, 3, 4]);
+    /// m.reshape_or_resize(3, 3, 0);
+    /// assert!(m == matrix::from(3, 3, &[1, 2, 0, 3, 4, 0, 0, 0, 0]));
+    ///
+    /// m.reshape_or_resize(1, 2, 0);
+    /// assert!(m == matrix::from(1, 2, &[1, 2]));
+    /// ```
+    ///
+    /// ```should_panic
+    /// use lars::matrix;
+    ///
+    /// // 2^63 * 2 overflows, it must not be taken for an empty shape.
+    /// matrix::from(0, 0, &[0; 0]).reshape_or_resize(1 << 63, 2, 0);
+    /// ```
+    pub fn reshape_or_resize(&mut self, rows: usize, cols: usize, fill: T) {
+        let size = match rows.checked_mul(cols) {
+            Some(size) => size,
+            None => panic!("A {}x{} matrix has too many elements!", rows, cols),
+        };
+        if self.content.len() == size {
+            self.reshape(rows, cols);
+            return
+        }
+
+        let mut pass = Matrix::<T>::new(rows, cols, fill);
+        for n in 0 .. rows.min(self.rows) {
+            for m in 0 .. cols.min(self.cols) {
+                pass.set(n, m, self.get(n, m));
+            }
+        }
+        *self = pass;
+    }
+
+    /// Formats the matrix like `Display`, but with every element rounded to `digits`
+    /// decimal places.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(2, 2, &[1.23456, 10.0, -0.5, 2.0 / 3.0]);
+    /// assert_eq!(m.to_string_precision(2), "[[ 1.23 10.00]\n [-0.50  0.67]]");
+    /// ```
+    pub fn to_string_precision(&self, digits: usize) -> String {
+        self.format_grid(|x| format!("{:.*}", digits, x.to_f64()))
+    }
+
+    /// Lays the formatted elements out as a grid, right-aligning every column to its
+    /// widest element.
+    fn format_grid(&self, cell: impl Fn(T) -> String) -> String {
+        let mut cells = Vec::with_capacity(self.rows * self.cols);
+        let mut widths = vec![0; self.cols];
+        for n in 0 .. self.rows {
+            for (m, width) in widths.iter_mut().enumerate() {
+                let c = cell(self.get(n, m));
+                if c.len() > *width {
+                    *width = c.len()
+                }
+                cells.push(c);
+            }
+        }
+
+        let mut s = String::from("[");
+        for n in 0 .. self.rows {
+            if n != 0 {
+                s.push_str("\n ");
+            }
+            s.push('[');
+            for m in 0 .. self.cols {
+                if m != 0 {
+                    s.push(' ');
+                }
+                s.push_str(&format!("{:>width$}", cells[n * self.cols + m], width = widths[m]));
+            }
+            s.push(']');
+        }
+        s.push(']');
+        s
+    }
+
     pub fn get_vector(&self) -> Vec<T> {
-        self.content.content.clone()
+        self.content.as_slice().to_vec()
+    }
+
+    /// Flattens the matrix into its row-major elements.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::vector;
+    ///
+    /// let m = matrix::from(2, 2, &[1, 2, 3, 4]);
+    /// let v = m.to_vector();
+    ///
+    /// assert!(v == vector::from(&[1, 2, 3, 4]));
+    /// assert!(v.to_matrix(2, 2) == m);
+    /// ```
+    pub fn to_vector(&self) -> Vector<T> {
+        self.content.clone()
     }
 
     pub fn get_cols(&self) -> usize {
@@ -432,23 +675,100 @@ impl<T: Number> Matrix<T> {
         self.rows.clone()
     }
 
+    /// The `(rows, cols)` dimensions of the matrix.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(2, 3, &[1, 2, 3, 4, 5, 6]);
+    /// let b = matrix::from(2, 3, &[0, 0, 0, 0, 0, 0]);
+    /// let c = matrix::from(3, 2, &[1, 2, 3, 4, 5, 6]);
+    ///
+    /// assert_eq!(a.shape(), (2, 3));
+    /// assert_eq!(c.shape(), (3, 2));
+    /// assert!(a.same_shape(&b));
+    /// assert!(!a.same_shape(&c));
+    /// ```
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn same_shape(&self, other: &Matrix<T>) -> bool {
+        self.shape() == other.shape()
+    }
+
+    /// Checks whether the matrix has no elements, i.e. zero rows or zero columns.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::matrix::Matrix;
+    ///
+    /// let empty = Matrix::new(3, 0, 1);
+    ///
+    /// assert!(empty.is_empty());
+    /// assert!(!matrix::from(1, 1, &[1]).is_empty());
+    /// assert!(empty.frobenius_inner(&empty) == 0);
+    /// assert_eq!(Matrix::new(0, 0, 1).trace(), 0);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.rows * self.cols == 0
+    }
+
+    /// Same shape and element-wise equality where NaN equals NaN and `-0.0` equals `0.0`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(1, 2, &[::std::f64::NAN, 0.0]);
+    /// let b = matrix::from(1, 2, &[::std::f64::NAN, -0.0]);
+    ///
+    /// assert!(a != b);
+    /// assert!(a.total_eq(&b));
+    /// assert!(!a.total_eq(&matrix::from(2, 1, &[::std::f64::NAN, 0.0])));
+    ///
+    /// let big = matrix::from(1, 2, &[1i64 << 53, 0]);
+    /// assert!(!big.total_eq(&matrix::from(1, 2, &[(1i64 << 53) + 1, 0])));
+    /// ```
+    pub fn total_eq(&self, other: &Matrix<T>) -> bool {
+        self.same_shape(other) && self.content.total_eq(&other.content)
+    }
+
+    /// Transposes the matrix. Square matrices are transposed in place by swapping
+    /// across the diagonal, other shapes are copied into a new buffer.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let mut m = matrix::from(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    /// let t = m.transposed();
+    /// m.transpose();
+    ///
+    /// assert!(m == t);
+    /// assert_eq!(m.get(0, 2), 7);
+    /// assert_eq!((m.get_rows(), m.get_cols()), (3, 3));
+    /// ```
     pub fn transpose(&mut self) {
-        let mut pass = self.content.clone();
-        for n in 0 .. self.rows {
-            for m in 0 .. self.cols {
-                pass[m * self.cols + n] = self.get(n, m);
+        if self.rows == self.cols {
+            for n in 0 .. self.rows {
+                for m in n + 1 .. self.cols {
+                    self.content.as_mut_slice().swap(n * self.cols + m, m * self.cols + n);
+                }
+            }
+        } else {
+            let mut pass = Vector::<T>::new(self.rows * self.cols, T::zero());
+            for n in 0 .. self.rows {
+                for m in 0 .. self.cols {
+                    pass[m * self.rows + n] = self.get(n, m);
+                }
             }
+            self.content = pass;
+            ::std::mem::swap(&mut self.rows, &mut self.cols);
         }
-        self.content = pass
     }
 
     pub fn transposed(&self) -> Matrix<T> {
         let mut pass = self.clone();
-        for n in 0 .. self.rows {
-            for m in 0 .. self.cols {
-                pass.content[m * self.cols + n] = self.get(n, m);
-            }
-        }
+        pass.transpose();
         pass
     }
 
@@ -464,41 +784,2278 @@ impl<T: Number> Matrix<T> {
         }
     }
 
-    pub fn powf(&self, pow: f64) {
-        self.content.powf(pow);
+    /// Product of the main diagonal of a square matrix, which is the determinant when
+    /// the matrix is triangular.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let upper = matrix::from(3, 3, &[2.0, 5.0, 1.0,
+    ///                                  0.0, 3.0, 7.0,
+    ///                                  0.0, 0.0, 4.0]);
+    /// assert_eq!(upper.diagonal_product(), 24.0);
+    /// assert!((upper.determinant() - 24.0).abs() < 1e-12);
+    ///
+    /// let dense = matrix::from(2, 2, &[1, 2, 3, 4]);
+    /// assert_eq!(dense.diagonal_product(), 4);
+    /// ```
+    pub fn diagonal_product(&self) -> T {
+        if self.rows == self.cols {
+            let mut product: T = T::one();
+            for n in 0 .. self.rows {
+                product = product * self.get(n, n);
+            }
+            product
+        } else {
+            panic!("Matrix must be a square!")
+        }
     }
-}
 
-pub fn from<T: Number>(rows: usize, cols: usize, elements: &[T]) -> Matrix<T> {
-    Matrix::<T> {
-        rows: rows,
-        cols: cols,
-        content: vector::from(elements),
+    /// Average of the main diagonal of a square matrix, `trace / n`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(3, 3, &[1, 9, 9, 9, 2, 9, 9, 9, 6]);
+    /// assert_eq!(m.mean_diagonal(), m.trace() as f64 / 3.0);
+    /// ```
+    pub fn mean_diagonal(&self) -> f64 {
+        self.trace().to_f64() / self.rows as f64
     }
-}
 
-pub fn identity<T: Number>(size: usize) -> Matrix<T> {
-    let mut i = Matrix::<T>::new(size, size, T::zero());
-    for n in 0 .. size {
-        i.set(n, n, T::one());
+    /// Determinant of a square matrix, computed from its LU decomposition. Triangular
+    /// matrices skip the elimination and use the product of the diagonal directly.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let lower = matrix::from(3, 3, &[0.1, 0.0, 0.0,
+    ///                                  7.0, 0.3, 0.0,
+    ///                                  9.0, 2.0, 0.7]);
+    /// let (sign, logdet) = lower.slogdet().unwrap();
+    ///
+    /// assert_eq!(lower.determinant(), lower.diagonal_product());
+    /// assert!((lower.determinant() - sign * logdet.exp()).abs() < 1e-12);
+    /// ```
+    pub fn determinant(&self) -> f64 {
+        if self.rows == self.cols
+                && (self.is_upper_triangular(0.0) || self.is_lower_triangular(0.0)) {
+            let mut det = 1.0;
+            for n in 0 .. self.rows {
+                det *= self.get(n, n).to_f64();
+            }
+            return det
+        }
+
+        match self.lu() {
+            Some((lu, _, sign)) => {
+                let mut det = sign;
+                for n in 0 .. self.rows {
+                    det *= lu.get(n, n);
+                }
+                det
+            }
+            None => panic!("Matrix must be a square!"),
+        }
     }
-    i
-}
 
-pub fn zeros_like<T: Number>(other: Matrix<T>) -> Matrix<T> {
-    Matrix::<T> {
-        rows: other.rows,
-        cols: other.cols,
-        content: Vector::new(other.rows * other.cols, T::zero()),
+    /// Returns the sign and the natural log of the absolute determinant, which stays
+    /// representable when the determinant itself would overflow or underflow.
+    /// Singular matrices give `(0.0, -inf)` and non-square matrices `None`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(3, 3, &[0.0, 2.0, 1.0,
+    ///                              1.0, 1.0, 0.0,
+    ///                              3.0, 0.0, 1.0]);
+    /// let (sign, logdet) = m.slogdet().unwrap();
+    ///
+    /// assert_eq!(sign, -1.0);
+    /// assert!((sign * logdet.exp() - m.determinant()).abs() < 1e-12);
+    /// assert!((m.determinant() + 5.0).abs() < 1e-12);
+    /// ```
+    pub fn slogdet(&self) -> Option<(f64, f64)> {
+        let (lu, _, mut sign) = self.lu()?;
+        if sign == 0.0 {
+            return Some((0.0, f64::NEG_INFINITY))
+        }
+
+        let mut logdet = 0.0;
+        for n in 0 .. self.rows {
+            let pivot = lu.get(n, n);
+            sign *= pivot.signum();
+            logdet += pivot.abs().ln();
+        }
+        Some((sign, logdet))
     }
-}
 
-pub fn zeros<T: Number>(rows: usize, cols: usize) -> Matrix<T> {
-    Matrix::<T> {
-        rows: rows,
-        cols: cols,
-        content: Vector::new(rows * cols, T::zero()),
+    /// Solves `A·x = b` through the LU decomposition of the square matrix `A`.
+    /// Returns `None` when `A` isn't square, is singular or `b` has the wrong length.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::vector;
+    ///
+    /// let a = matrix::from(2, 2, &[2.0, 1.0, 1.0, 3.0]);
+    /// let x = a.solve(&vector::from(&[3.0, 5.0])).unwrap();
+    ///
+    /// assert!((x[0] - 0.8).abs() < 1e-12);
+    /// assert!((x[1] - 1.4).abs() < 1e-12);
+    /// assert!(matrix::from(2, 2, &[1.0, 2.0, 2.0, 4.0]).solve(&x).is_none());
+    /// ```
+    pub fn solve(&self, rhs: &Vector<T>) -> Option<Vector<f64>> {
+        let (lu, perm, sign) = self.lu()?;
+        if sign == 0.0 || rhs.len() != self.rows {
+            return None
+        }
+
+        let size = self.rows;
+        let mut x = Vector::<f64>::new(size, 0.0);
+        for n in 0 .. size {
+            let mut sum = rhs[perm[n]].to_f64();
+            for k in 0 .. n {
+                sum -= lu.get(n, k) * x[k];
+            }
+            x[n] = sum;
+        }
+        for n in (0 .. size).rev() {
+            let mut sum = x[n];
+            for k in n + 1 .. size {
+                sum -= lu.get(n, k) * x[k];
+            }
+            x[n] = sum / lu.get(n, n);
+        }
+        Some(x)
+    }
+
+    /// Solves `A·x = b` with Cramer's rule, `xᵢ = det(Aᵢ) / det(A)` where `Aᵢ` has its
+    /// `i`th column replaced by `b`. Takes `n + 1` determinants, so prefer `solve`
+    /// beyond small systems.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::vector;
+    ///
+    /// let a = matrix::from(2, 2, &[2.0, 1.0, 1.0, 3.0]);
+    /// let x = a.cramer_solve(&vector::from(&[3.0, 5.0])).unwrap();
+    ///
+    /// assert!((x[0] - 0.8).abs() < 1e-12);
+    /// assert!((x[1] - 1.4).abs() < 1e-12);
+    /// assert!(matrix::from(2, 2, &[1.0, 2.0, 2.0, 4.0]).cramer_solve(&x).is_none());
+    /// ```
+    pub fn cramer_solve(&self, b: &Vector<f64>) -> Option<Vector<f64>> {
+        if self.rows != self.cols || b.len() != self.rows {
+            return None
+        }
+        let a = self.to_f64();
+        let det = a.determinant();
+        if det == 0.0 {
+            return None
+        }
+
+        let mut x = Vector::<f64>::new(self.rows, 0.0);
+        for i in 0 .. self.cols {
+            let mut replaced = a.clone();
+            for n in 0 .. self.rows {
+                replaced.set(n, i, b[n]);
+            }
+            x[i] = replaced.determinant() / det;
+        }
+        Some(x)
+    }
+
+    /// Reduced row echelon form through Gauss-Jordan elimination with partial
+    /// pivoting. Entries within `1e-10` times the largest absolute element are treated
+    /// as zero.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(3, 4, &[1.0, 2.0, -1.0, -4.0,
+    ///                              2.0, 3.0, -1.0, -11.0,
+    ///                              -2.0, 0.0, -3.0, 22.0]);
+    ///
+    /// let expected = matrix::from(3, 4, &[1.0, 0.0, 0.0, -8.0,
+    ///                                     0.0, 1.0, 0.0, 1.0,
+    ///                                     0.0, 0.0, 1.0, -2.0]);
+    /// assert!((m.rref() - expected).to_vector().inf_norm() < 1e-12);
+    ///
+    /// let rank_one = matrix::from(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+    /// assert!(rank_one.rref() == matrix::from(2, 2, &[1.0, 2.0, 0.0, 0.0]));
+    /// ```
+    pub fn rref(&self) -> Matrix<f64> {
+        self.rref_pivots().0
+    }
+
+    /// Number of linearly independent rows (or columns), the pivot count of `rref`.
+    pub fn rank(&self) -> usize {
+        self.rref_pivots().1.len()
+    }
+
+    /// Basis of the null space, the vectors `v` with `A·v = 0`, read off `rref` with
+    /// one vector per free column. Full column rank gives an empty basis.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(3, 4, &[1.0, 2.0, 0.0, 1.0,
+    ///                              2.0, 4.0, 1.0, 1.0,
+    ///                              3.0, 6.0, 1.0, 2.0]);
+    /// let basis = a.null_space();
+    ///
+    /// assert_eq!(a.rank(), 2);
+    /// assert_eq!(basis.len(), 4 - a.rank());
+    /// for v in &basis {
+    ///     let product = a.mul_ref(&v.to_matrix(4, 1));
+    ///     assert!(product.to_vector().inf_norm() < 1e-12);
+    /// }
+    /// assert!(matrix::identity::<f64>(3).null_space().is_empty());
+    /// ```
+    pub fn null_space(&self) -> Vec<Vector<f64>> {
+        let (r, pivots) = self.rref_pivots();
+        let mut basis = Vec::new();
+        for free in 0 .. self.cols {
+            if pivots.contains(&free) {
+                continue
+            }
+
+            let mut v = Vector::<f64>::new(self.cols, 0.0);
+            v[free] = 1.0;
+            for (row, &pivot) in pivots.iter().enumerate() {
+                v[pivot] = -r.get(row, free);
+            }
+            basis.push(v);
+        }
+        basis
+    }
+
+    /// Basis of the column space, the columns of the original matrix at the pivot
+    /// positions of `rref`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::vector;
+    ///
+    /// let a = matrix::from(3, 4, &[1.0, 2.0, 0.0, 1.0,
+    ///                              2.0, 4.0, 1.0, 1.0,
+    ///                              3.0, 6.0, 1.0, 2.0]);
+    /// let basis = a.column_space();
+    ///
+    /// assert_eq!(basis.len(), a.rank());
+    /// assert!(basis[0] == vector::from(&[1.0, 2.0, 3.0]));
+    /// assert!(basis[1] == vector::from(&[0.0, 1.0, 1.0]));
+    /// ```
+    pub fn column_space(&self) -> Vec<Vector<f64>> {
+        let pivots = self.rref_pivots().1;
+        let mut basis = Vec::with_capacity(pivots.len());
+        for col in pivots {
+            let mut v = Vector::<f64>::new(self.rows, 0.0);
+            for n in 0 .. self.rows {
+                v[n] = self.get(n, col).to_f64();
+            }
+            basis.push(v);
+        }
+        basis
+    }
+
+    /// Reduced row echelon form along with the pivot column of every non-zero row.
+    fn rref_pivots(&self) -> (Matrix<f64>, Vec<usize>) {
+        let mut a = self.to_f64();
+        let tol = 1e-10 * a.content.inf_norm();
+        let mut pivots = Vec::new();
+        let mut row = 0;
+        for col in 0 .. a.cols {
+            if row == a.rows {
+                break
+            }
+
+            let mut pivot = row;
+            for n in row + 1 .. a.rows {
+                if a.get(n, col).abs() > a.get(pivot, col).abs() {
+                    pivot = n
+                }
+            }
+            if a.get(pivot, col).abs() <= tol {
+                for n in row .. a.rows {
+                    a.set(n, col, 0.0);
+                }
+                continue
+            }
+            a.swap_rows(pivot, row);
+
+            let p = a.get(row, col);
+            for m in 0 .. a.cols {
+                let x = a.get(row, m) / p;
+                a.set(row, m, x);
+            }
+            for n in 0 .. a.rows {
+                let factor = a.get(n, col);
+                if n != row && factor != 0.0 {
+                    for m in 0 .. a.cols {
+                        let x = a.get(n, m) - factor * a.get(row, m);
+                        a.set(n, m, if x.abs() <= tol { 0.0 } else { x });
+                    }
+                }
+            }
+            pivots.push(col);
+            row += 1;
+        }
+        (a, pivots)
+    }
+
+    /// Inverse of a square, non-singular matrix, solved column by column from a
+    /// single LU decomposition.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(2, 2, &[4.0, 7.0, 2.0, 6.0]);
+    /// let inv = a.inverse().unwrap();
+    /// let i = a * inv;
+    ///
+    /// assert!((i.get(0, 0) - 1.0).abs() < 1e-12 && i.get(0, 1).abs() < 1e-12);
+    /// assert!(i.get(1, 0).abs() < 1e-12 && (i.get(1, 1) - 1.0).abs() < 1e-12);
+    /// assert!(matrix::from(2, 2, &[1.0, 2.0, 2.0, 4.0]).inverse().is_none());
+    /// ```
+    pub fn inverse(&self) -> Option<Matrix<f64>> {
+        let (lu, perm, sign) = self.lu()?;
+        if sign == 0.0 {
+            return None
+        }
+
+        let size = self.rows;
+        let mut pass = Matrix::<f64>::new(size, size, 0.0);
+        for c in 0 .. size {
+            for (n, &row) in perm.iter().enumerate() {
+                let mut sum = if row == c { 1.0 } else { 0.0 };
+                for k in 0 .. n {
+                    sum -= lu.get(n, k) * pass.get(k, c);
+                }
+                pass.set(n, c, sum);
+            }
+            for n in (0 .. size).rev() {
+                let mut sum = pass.get(n, c);
+                for k in n + 1 .. size {
+                    sum -= lu.get(n, k) * pass.get(k, c);
+                }
+                pass.set(n, c, sum / lu.get(n, n));
+            }
+        }
+        Some(pass)
+    }
+
+    /// Checks whether the matrix is symmetric and positive definite by attempting a
+    /// Cholesky decomposition `A = L·Lᵀ`, which succeeds exactly for such matrices.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let spd = matrix::from(2, 2, &[4.0, 2.0, 2.0, 3.0]);
+    /// let indefinite = matrix::from(2, 2, &[1.0, 2.0, 2.0, 1.0]);
+    /// let asymmetric = matrix::from(2, 2, &[4.0, 1.0, 0.0, 3.0]);
+    ///
+    /// assert!(spd.is_positive_definite());
+    /// assert!(!indefinite.is_positive_definite());
+    /// assert!(!asymmetric.is_positive_definite());
+    /// ```
+    pub fn is_positive_definite(&self) -> bool {
+        if self.rows != self.cols {
+            return false
+        }
+        for n in 0 .. self.rows {
+            for m in n + 1 .. self.cols {
+                if self.get(n, m).to_f64() != self.get(m, n).to_f64() {
+                    return false
+                }
+            }
+        }
+
+        let size = self.rows;
+        let mut l = Matrix::<f64>::new(size, size, 0.0);
+        for n in 0 .. size {
+            for m in 0 ..= n {
+                let mut sum = self.get(n, m).to_f64();
+                for k in 0 .. m {
+                    sum -= l.get(n, k) * l.get(m, k);
+                }
+                if n == m {
+                    if sum <= 0.0 {
+                        return false
+                    }
+                    l.set(n, n, sum.sqrt());
+                } else {
+                    l.set(n, m, sum / l.get(m, m));
+                }
+            }
+        }
+        true
+    }
+
+    /// LU decomposition with partial pivoting. Returns the combined `L` (below the
+    /// diagonal, unit diagonal implied) and `U` factors, the row permutation and the
+    /// permutation sign, which is `0.0` when the matrix is singular.
+    fn lu(&self) -> Option<(Matrix<f64>, Vec<usize>, f64)> {
+        if self.rows != self.cols {
+            return None
+        }
+
+        let size = self.rows;
+        let mut lu = self.to_f64();
+        let mut perm: Vec<usize> = (0 .. size).collect();
+        let mut sign = 1.0;
+        for k in 0 .. size {
+            let mut pivot = k;
+            for n in k + 1 .. size {
+                if lu.get(n, k).abs() > lu.get(pivot, k).abs() {
+                    pivot = n
+                }
+            }
+            if lu.get(pivot, k) == 0.0 {
+                return Some((lu, perm, 0.0))
+            }
+            if pivot != k {
+                lu.swap_rows(pivot, k);
+                perm.swap(pivot, k);
+                sign = -sign;
+            }
+
+            for n in k + 1 .. size {
+                let factor = lu.get(n, k) / lu.get(k, k);
+                lu.set(n, k, factor);
+                for m in k + 1 .. size {
+                    let x = lu.get(n, m) - factor * lu.get(k, m);
+                    lu.set(n, m, x);
+                }
+            }
+        }
+        Some((lu, perm, sign))
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for m in 0 .. self.cols {
+            self.content.as_mut_slice().swap(a * self.cols + m, b * self.cols + m);
+        }
+    }
+
+    /// Computes `tr(A·B)` as the sum of the element-wise products of `A` and `Bᵀ`,
+    /// without forming the full product.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(2, 2, &[1, 2, 3, 4]);
+    /// let b = matrix::from(2, 2, &[5, 6, 7, 8]);
+    ///
+    /// assert_eq!(a.trace_product(&b), (a.clone() * b).trace());
+    /// ```
+    pub fn trace_product(&self, other: &Matrix<T>) -> T {
+        if self.cols == other.rows
+                && self.rows == other.cols {
+            let mut sum: T = T::zero();
+            for n in 0 .. self.rows {
+                for k in 0 .. self.cols {
+                    sum = sum + self.get(n, k) * other.get(k, n);
+                }
+            }
+            sum
+        } else {
+            panic!("Can't multiply matrices of incompatible dimensions!")
+        }
+    }
+
+    /// Frobenius inner product `Σᵢⱼ aᵢⱼbᵢⱼ` of two equally shaped matrices.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(2, 2, &[1, 2, 3, 4]);
+    /// let b = matrix::from(2, 2, &[5, 6, 7, 8]);
+    ///
+    /// assert_eq!(a.frobenius_inner(&b), 5 + 12 + 21 + 32);
+    ///
+    /// let norm = a.to_vector().norm();
+    /// assert_eq!(a.frobenius_inner(&a) as f64, norm * norm);
+    /// ```
+    pub fn frobenius_inner(&self, other: &Matrix<T>) -> T {
+        if self.same_shape(other) {
+            self.content.dot(&other.content)
+        } else {
+            panic!("Can't multiply matrices of different dimensions!")
+        }
+    }
+
+    /// Computes `Σᵢⱼ aᵢⱼ²`, the squared Frobenius norm.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(2, 2, &[1, 2, 3, 4]);
+    /// let norm = m.to_vector().norm();
+    ///
+    /// assert_eq!(m.sum_of_squares(), 30.0);
+    /// assert!((m.sum_of_squares() - norm * norm).abs() < 1e-12);
+    /// ```
+    pub fn sum_of_squares(&self) -> f64 {
+        self.content.sum_of_squares()
+    }
+
+    /// Same as `self + rhs`, but borrows both operands so neither has to be cloned.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(2, 2, &[1, 2, 3, 4]);
+    /// let b = matrix::from(2, 2, &[5, 6, 7, 8]);
+    ///
+    /// let sum = a.add_ref(&b);
+    /// let product = a.mul_ref(&b);
+    ///
+    /// // Both operands are still usable
+    /// assert!(sum == a.clone() + b.clone());
+    /// assert!(product == a * b);
+    /// ```
+    pub fn add_ref(&self, rhs: &Matrix<T>) -> Matrix<T> {
+        if self.same_shape(rhs) {
+            Matrix::<T> {
+                rows: self.rows,
+                cols: self.cols,
+                content: self.content.add_ref(&rhs.content),
+            }
+        } else {
+            panic!("Can't add matrices of different dimensions!");
+        }
+    }
+
+    pub fn sub_ref(&self, rhs: &Matrix<T>) -> Matrix<T> {
+        if self.same_shape(rhs) {
+            Matrix::<T> {
+                rows: self.rows,
+                cols: self.cols,
+                content: self.content.sub_ref(&rhs.content),
+            }
+        } else {
+            panic!("Can't subtract matrices of different dimensions!");
+        }
+    }
+
+    /// Non-panicking `add_ref`, returning a `ShapeError` on mismatched dimensions.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(1, 2, &[1, 2]);
+    ///
+    /// assert!(a.checked_add(&a).unwrap() == matrix::from(1, 2, &[2, 4]));
+    /// assert!(a.checked_sub(&a).unwrap() == matrix::from(1, 2, &[0, 0]));
+    /// assert!(a.checked_add(&a.transposed()).is_err());
+    /// ```
+    pub fn checked_add(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        error::check_same_shape(self, rhs)?;
+        Ok(self.add_ref(rhs))
+    }
+
+    /// Non-panicking `sub_ref`, returning a `ShapeError` on mismatched dimensions.
+    pub fn checked_sub(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        error::check_same_shape(self, rhs)?;
+        Ok(self.sub_ref(rhs))
+    }
+
+    /// Matrix product `self · rhs` of a `rows` by `k` and a `k` by `cols` matrix,
+    /// giving a `rows` by `cols` matrix; this is also what `*` computes. Sums are
+    /// accumulated in `T` like `Vector::dot`, so integer types wrap (or panic in debug
+    /// builds) on overflow.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(1, 3, &[1, 2, 3]);
+    /// let b = matrix::from(3, 2, &[1, 0, 0, 1, 1, 1]);
+    ///
+    /// assert!(a.mul_ref(&b) == matrix::from(1, 2, &[4, 5]));
+    /// assert!(a * b == matrix::from(1, 2, &[4, 5]));
+    /// ```
+    pub fn mul_ref(&self, rhs: &Matrix<T>) -> Matrix<T> {
+        let mut pass = Matrix::<T>::new(self.rows, rhs.cols, T::zero());
+        self.matmul_into(rhs, &mut pass);
+        pass
+    }
+
+    /// Sums `self[n][k] / rhs[k][m]` like the product sums `self[n][k] * rhs[k][m]`;
+    /// this is what `/` computes. `rhs` must have as many columns as `self`, and the
+    /// result has the shape of `self`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(2, 2, &[6.0, 8.0, 4.0, 2.0]);
+    /// let b = matrix::from(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+    ///
+    /// assert!(a / b == matrix::from(2, 2, &[10.0, 5.0, 5.0, 2.5]));
+    /// ```
+    pub fn div_ref(&self, rhs: &Matrix<T>) -> Matrix<T> {
+        if self.cols == rhs.cols {
+            let mut pass = Matrix::<T>::new(self.rows, self.cols, T::zero());
+            for n in 0 .. self.rows {
+                for m in 0 .. rhs.cols {
+                    let mut product: T = T::zero();
+                    for k in 0 .. self.cols {
+                        product = product + self.get(n, k) / rhs.get(k, m);
+                    }
+                    pass.set(n, m, product);
+                }
+            }
+            pass
+        } else {
+            panic!("Can't divide matrices of different dimensions!")
+        }
+    }
+
+    /// Writes the product `self · rhs` into `out`, which must already be `self.rows`
+    /// by `rhs.cols`, avoiding an allocation per multiplication. Overflows the same
+    /// way as `mul_ref`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(2, 3, &[1, 2, 3, 4, 5, 6]);
+    /// let b = matrix::from(3, 2, &[7, 8, 9, 10, 11, 12]);
+    ///
+    /// let mut out = matrix::zeros(2, 2);
+    /// a.matmul_into(&b, &mut out);
+    ///
+    /// assert!(out == matrix::from(2, 2, &[58, 64, 139, 154]));
+    /// assert!(out == a * b);
+    /// ```
+    pub fn matmul_into(&self, rhs: &Matrix<T>, out: &mut Matrix<T>) {
+        if self.cols != rhs.rows {
+            panic!("Can't multiply matrices of different dimensions!")
+        }
+        if out.rows != self.rows
+                || out.cols != rhs.cols {
+            panic!("Output matrix has the wrong dimensions!")
+        }
+
+        for n in 0 .. self.rows {
+            for m in 0 .. rhs.cols {
+                let mut product: T = T::zero();
+                for k in 0 .. self.cols {
+                    product = product + self.get(n, k) * rhs.get(k, m);
+                }
+                out.set(n, m, product);
+            }
+        }
+    }
+
+    /// Computes `Aᵀ · B` without materializing the transpose of `A`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(3, 2, &[1, 2, 3, 4, 5, 6]);
+    /// let b = matrix::from(3, 2, &[7, 8, 9, 10, 11, 12]);
+    ///
+    /// assert!(a.transpose_mul(&b) == a.transposed() * b);
+    /// ```
+    pub fn transpose_mul(&self, other: &Matrix<T>) -> Matrix<T> {
+        if self.rows != other.rows {
+            panic!("Can't multiply matrices of different dimensions!")
+        }
+
+        let mut pass = Matrix::<T>::new(self.cols, other.cols, T::zero());
+        for n in 0 .. self.cols {
+            for m in 0 .. other.cols {
+                let mut product: T = T::zero();
+                for k in 0 .. self.rows {
+                    product = product + self.get(k, n) * other.get(k, m);
+                }
+                pass.set(n, m, product);
+            }
+        }
+        pass
+    }
+
+    /// Computes `A · diag(d)` in O(n²) by scaling every column of `A` by the matching
+    /// diagonal entry.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::vector;
+    ///
+    /// let a = matrix::from(2, 3, &[1, 2, 3, 4, 5, 6]);
+    /// let d = vector::from(&[2, 0, -1]);
+    ///
+    /// assert!(a.mul_diagonal(&d) == matrix::from(2, 3, &[2, 0, -3, 8, 0, -6]));
+    /// assert!(a.mul_diagonal(&d) == a.clone() * matrix::diagonal(&d));
+    /// ```
+    pub fn mul_diagonal(&self, diag: &Vector<T>) -> Matrix<T> {
+        if self.cols != diag.len() {
+            panic!("Can't multiply matrices of different dimensions!")
+        }
+
+        let mut pass = self.clone();
+        for n in 0 .. self.rows {
+            for m in 0 .. self.cols {
+                pass.set(n, m, self.get(n, m) * diag[m]);
+            }
+        }
+        pass
+    }
+
+    /// Discrete 2D convolution with a (small) kernel.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::matrix::ConvMode;
+    ///
+    /// let m = matrix::from(3, 4, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+    /// let blur = matrix::Matrix::new(3, 3, 1);
+    ///
+    /// let same = m.convolve2d(&blur, ConvMode::Same);
+    /// assert_eq!((same.get_rows(), same.get_cols()), (3, 4));
+    /// assert_eq!(same.get(1, 1), 1 + 2 + 3 + 5 + 6 + 7 + 9 + 10 + 11);
+    /// assert_eq!(same.get(0, 0), 1 + 2 + 5 + 6);
+    ///
+    /// let full = m.convolve2d(&blur, ConvMode::Full);
+    /// assert_eq!((full.get_rows(), full.get_cols()), (5, 6));
+    /// assert!(m.convolve2d(&blur, ConvMode::Valid) == matrix::from(1, 2, &[54, 63]));
+    /// ```
+    pub fn convolve2d(&self, kernel: &Matrix<T>, mode: ConvMode) -> Matrix<T> {
+        if self.rows == 0 || self.cols == 0 || kernel.rows == 0 || kernel.cols == 0 {
+            return Matrix::<T>::new(0, 0, T::zero())
+        }
+
+        let mut full = Matrix::<T>::new(self.rows + kernel.rows - 1, self.cols + kernel.cols - 1, T::zero());
+        for n in 0 .. self.rows {
+            for m in 0 .. self.cols {
+                for a in 0 .. kernel.rows {
+                    for b in 0 .. kernel.cols {
+                        let sum = full.get(n + a, m + b) + self.get(n, m) * kernel.get(a, b);
+                        full.set(n + a, m + b, sum);
+                    }
+                }
+            }
+        }
+
+        let (top, left, rows, cols) = match mode {
+            ConvMode::Full => return full,
+            ConvMode::Same => ((kernel.rows - 1) / 2, (kernel.cols - 1) / 2, self.rows, self.cols),
+            ConvMode::Valid => (
+                kernel.rows - 1,
+                kernel.cols - 1,
+                (self.rows + 1).saturating_sub(kernel.rows),
+                (self.cols + 1).saturating_sub(kernel.cols),
+            ),
+        };
+
+        let mut pass = Matrix::<T>::new(rows, cols, T::zero());
+        for n in 0 .. rows {
+            for m in 0 .. cols {
+                pass.set(n, m, full.get(top + n, left + m));
+            }
+        }
+        pass
+    }
+
+    /// Checks whether the matrix is square and `Aᵀ·A` is within `tol` of the identity.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let (s, c) = 0.3f64.sin_cos();
+    /// let rotation = matrix::from(2, 2, &[c, -s, s, c]);
+    /// assert!(rotation.is_orthogonal(1e-12));
+    ///
+    /// let scaled = matrix::identity::<f64>(2) * 2.0;
+    /// assert!(!scaled.is_orthogonal(1e-12));
+    /// ```
+    pub fn is_orthogonal(&self, tol: f64) -> bool {
+        if self.rows != self.cols {
+            return false
+        }
+
+        let a = self.to_f64();
+        let product = a.transpose_mul(&a);
+        for n in 0 .. self.rows {
+            for m in 0 .. self.cols {
+                let expected = if n == m { 1.0 } else { 0.0 };
+                if (product.get(n, m) - expected).abs() > tol {
+                    return false
+                }
+            }
+        }
+        true
+    }
+
+    /// Checks whether every element below the main diagonal is within `tol` of zero.
+    /// Also applies to non-square matrices, e.g. the `R` factor of a QR decomposition.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let upper = matrix::from(3, 3, &[1.0, 2.0, 3.0,
+    ///                                  0.0, 4.0, 5.0,
+    ///                                  1e-12, 0.0, 6.0]);
+    /// assert!(upper.is_upper_triangular(1e-9));
+    /// assert!(!upper.is_lower_triangular(1e-9));
+    /// assert!(upper.transposed().is_lower_triangular(1e-9));
+    ///
+    /// let dense = matrix::from(2, 2, &[1.0, 2.0, 3.0, 4.0]);
+    /// assert!(!dense.is_upper_triangular(1e-9));
+    /// assert!(!dense.is_lower_triangular(1e-9));
+    /// ```
+    pub fn is_upper_triangular(&self, tol: f64) -> bool {
+        for n in 1 .. self.rows {
+            for m in 0 .. n.min(self.cols) {
+                if self.get(n, m).to_f64().abs() > tol {
+                    return false
+                }
+            }
+        }
+        true
+    }
+
+    /// Checks whether the matrix is a (right) stochastic matrix: no negative entries
+    /// and every row summing to one within `tol`, like a Markov transition matrix.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let p = matrix::from(2, 2, &[0.9, 0.1, 0.4, 0.6]);
+    /// assert!(p.is_row_stochastic(1e-12));
+    /// assert!(!p.is_column_stochastic(1e-12));
+    /// assert!(p.transposed().is_column_stochastic(1e-12));
+    ///
+    /// let leaky = matrix::from(2, 2, &[0.9, 0.1, 0.4, 0.5]);
+    /// assert!(!leaky.is_row_stochastic(1e-12));
+    /// assert!(!matrix::from(1, 2, &[1.5, -0.5]).is_row_stochastic(1e-12));
+    /// ```
+    pub fn is_row_stochastic(&self, tol: f64) -> bool {
+        for n in 0 .. self.rows {
+            let mut sum = 0.0;
+            for m in 0 .. self.cols {
+                let x = self.get(n, m).to_f64();
+                if x < 0.0 {
+                    return false
+                }
+                sum += x;
+            }
+            if (sum - 1.0).abs() > tol {
+                return false
+            }
+        }
+        true
+    }
+
+    /// Checks for no negative entries and every column summing to one within `tol`.
+    pub fn is_column_stochastic(&self, tol: f64) -> bool {
+        self.transposed().is_row_stochastic(tol)
+    }
+
+    /// Stationary distribution `π = π·P` of a row-stochastic transition matrix, the
+    /// left eigenvector for eigenvalue 1 normalized to sum to one. Solves
+    /// `(Pᵀ - I)·π = 0` with one equation replaced by `Σπᵢ = 1`. Returns `None` for
+    /// non-stochastic matrices and chains without a unique stationary distribution.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let p = matrix::from(2, 2, &[0.9, 0.1,
+    ///                              0.5, 0.5]);
+    /// let pi = p.stationary_distribution().unwrap();
+    ///
+    /// assert!((pi[0] - 5.0 / 6.0).abs() < 1e-12);
+    /// assert!((pi[1] - 1.0 / 6.0).abs() < 1e-12);
+    ///
+    /// // Two disconnected states have no unique distribution
+    /// assert!(matrix::identity::<f64>(2).stationary_distribution().is_none());
+    /// ```
+    pub fn stationary_distribution(&self) -> Option<Vector<f64>> {
+        if self.rows != self.cols || self.rows == 0 || !self.is_row_stochastic(1e-9) {
+            return None
+        }
+
+        let size = self.rows;
+        let mut a = self.to_f64().transposed().sub_ref(&identity(size));
+        let mut rhs = Vector::<f64>::new(size, 0.0);
+        for m in 0 .. size {
+            a.set(size - 1, m, 1.0);
+        }
+        rhs[size - 1] = 1.0;
+        a.solve(&rhs)
+    }
+
+    /// Checks whether every element above the main diagonal is within `tol` of zero.
+    pub fn is_lower_triangular(&self, tol: f64) -> bool {
+        for n in 0 .. self.rows {
+            for m in n + 1 .. self.cols {
+                if self.get(n, m).to_f64().abs() > tol {
+                    return false
+                }
+            }
+        }
+        true
+    }
+
+    /// Checks whether every element satisfies `pred`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let positive = matrix::from(2, 2, &[1, 2, 3, 4]);
+    /// let mixed = matrix::from(2, 2, &[1, -2, 3, 4]);
+    ///
+    /// assert!(positive.all(|x| x > 0));
+    /// assert!(!mixed.all(|x| x > 0));
+    /// assert!(mixed.any(|x| x < 0));
+    /// ```
+    pub fn all(&self, pred: impl Fn(T) -> bool) -> bool {
+        self.content.all(pred)
+    }
+
+    /// Checks whether at least one element satisfies `pred`.
+    pub fn any(&self, pred: impl Fn(T) -> bool) -> bool {
+        self.content.any(pred)
+    }
+
+    /// Counts the elements satisfying `pred`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(3, 3, &[1, 0, 0, 0, 2, 0, 0, 3, 4]);
+    /// assert_eq!(m.count(|x| x != 0), 4);
+    /// ```
+    pub fn count(&self, pred: impl Fn(T) -> bool) -> usize {
+        self.content.count(pred)
+    }
+
+    /// Returns the (row, col) positions of the nonzero elements in row-major order.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(2, 3, &[0, 5, 0, 7, 0, 0]);
+    ///
+    /// assert_eq!(m.nonzero_indices(), vec![(0, 1), (1, 0)]);
+    /// assert_eq!(m.sparsity(), 4.0 / 6.0);
+    /// ```
+    pub fn nonzero_indices(&self) -> Vec<(usize, usize)> {
+        let mut indices = Vec::new();
+        for n in self.content.nonzero_indices() {
+            indices.push((n / self.cols, n % self.cols));
+        }
+        indices
+    }
+
+    /// Fraction of the elements that are zero, `0.0` for an empty matrix.
+    pub fn sparsity(&self) -> f64 {
+        self.content.sparsity()
+    }
+
+    /// Removes row `r`, shrinking the matrix by one row.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let mut m = matrix::from(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    ///
+    /// m.delete_row(1);
+    /// assert!(m == matrix::from(2, 3, &[1, 2, 3, 7, 8, 9]));
+    ///
+    /// m.delete_col(0);
+    /// assert!(m == matrix::from(2, 2, &[2, 3, 8, 9]));
+    /// ```
+    pub fn delete_row(&mut self, r: usize) {
+        if r < self.rows {
+            self.content.as_mut_vec().drain(r * self.cols .. (r + 1) * self.cols);
+            self.rows -= 1;
+        } else {
+            panic!("Matrix row {} out of bounds!", r)
+        }
+    }
+
+    /// Removes column `c`, shrinking the matrix by one column.
+    pub fn delete_col(&mut self, c: usize) {
+        if c < self.cols {
+            for n in (0 .. self.rows).rev() {
+                self.content.as_mut_vec().remove(n * self.cols + c);
+            }
+            self.cols -= 1;
+        } else {
+            panic!("Matrix column {} out of bounds!", c)
+        }
+    }
+
+    /// Inserts `row` before row `at`, growing the matrix by one row.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::vector;
+    ///
+    /// let mut m = matrix::from(2, 2, &[1, 2, 5, 6]);
+    ///
+    /// m.insert_row(1, &vector::from(&[3, 4]));
+    /// assert!(m == matrix::from(3, 2, &[1, 2, 3, 4, 5, 6]));
+    ///
+    /// m.insert_col(2, &vector::from(&[0, 0, 0]));
+    /// assert!(m == matrix::from(3, 3, &[1, 2, 0, 3, 4, 0, 5, 6, 0]));
+    /// ```
+    pub fn insert_row(&mut self, at: usize, row: &Vector<T>) {
+        if at > self.rows {
+            panic!("Matrix row {} out of bounds!", at)
+        }
+        if row.len() != self.cols {
+            panic!("Row must have the same dimension as the matrix columns!")
+        }
+
+        let start = at * self.cols;
+        self.content.as_mut_vec().splice(start .. start, row.iter().cloned());
+        self.rows += 1;
+    }
+
+    /// Inserts `col` before column `at`, growing the matrix by one column.
+    pub fn insert_col(&mut self, at: usize, col: &Vector<T>) {
+        if at > self.cols {
+            panic!("Matrix column {} out of bounds!", at)
+        }
+        if col.len() != self.rows {
+            panic!("Column must have the same dimension as the matrix rows!")
+        }
+
+        for n in (0 .. self.rows).rev() {
+            self.content.as_mut_vec().insert(n * self.cols + at, col[n]);
+        }
+        self.cols += 1;
+    }
+
+    /// Returns the matrix with a column of ones prepended, the usual design matrix
+    /// for fitting an intercept.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(2, 2, &[3, 4, 5, 6]);
+    ///
+    /// assert!(m.with_bias_column() == matrix::from(2, 3, &[1, 3, 4, 1, 5, 6]));
+    /// ```
+    pub fn with_bias_column(&self) -> Matrix<T> {
+        let mut pass = self.clone();
+        pass.insert_col(0, &Vector::new(self.rows, T::one()));
+        pass
+    }
+
+    /// Returns the matrix with its rows in reverse order.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(2, 3, &[1, 2, 3, 4, 5, 6]);
+    ///
+    /// assert!(m.flip_rows() == matrix::from(2, 3, &[4, 5, 6, 1, 2, 3]));
+    /// assert!(m.flip_cols() == matrix::from(2, 3, &[3, 2, 1, 6, 5, 4]));
+    /// ```
+    pub fn flip_rows(&self) -> Matrix<T> {
+        let mut pass = self.clone();
+        for n in 0 .. self.rows {
+            for m in 0 .. self.cols {
+                pass.set(self.rows - 1 - n, m, self.get(n, m));
+            }
+        }
+        pass
+    }
+
+    /// Returns the matrix with the elements of each row in reverse order.
+    pub fn flip_cols(&self) -> Matrix<T> {
+        let mut pass = self.clone();
+        for n in 0 .. self.rows {
+            for m in 0 .. self.cols {
+                pass.set(n, self.cols - 1 - m, self.get(n, m));
+            }
+        }
+        pass
+    }
+
+    /// Rotates the matrix clockwise by `times` quarter-turns, counterclockwise for
+    /// negative `times`. Odd turns swap the dimensions.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(2, 3, &[1, 2, 3,
+    ///                              4, 5, 6]);
+    ///
+    /// assert!(m.rotate90(1) == matrix::from(3, 2, &[4, 1,
+    ///                                               5, 2,
+    ///                                               6, 3]));
+    /// assert!(m.rotate90(-1) == matrix::from(3, 2, &[3, 6,
+    ///                                                2, 5,
+    ///                                                1, 4]));
+    /// assert!(m.rotate90(2) == m.flip_rows().flip_cols());
+    /// assert!(m.rotate90(4) == m);
+    /// ```
+    pub fn rotate90(&self, times: i32) -> Matrix<T> {
+        match times.rem_euclid(4) {
+            1 => self.transposed().flip_cols(),
+            2 => self.flip_rows().flip_cols(),
+            3 => self.transposed().flip_rows(),
+            _ => self.clone(),
+        }
+    }
+
+    /// Surrounds the matrix with a border of `value`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(2, 2, &[1, 2, 3, 4]);
+    /// let padded = m.pad(1, 1, 1, 1, 0);
+    ///
+    /// assert!(padded == matrix::from(4, 4, &[
+    ///     0, 0, 0, 0,
+    ///     0, 1, 2, 0,
+    ///     0, 3, 4, 0,
+    ///     0, 0, 0, 0,
+    /// ]));
+    /// ```
+    pub fn pad(&self, top: usize, bottom: usize, left: usize, right: usize, value: T) -> Matrix<T> {
+        let mut pass = Matrix::<T>::new(top + self.rows + bottom, left + self.cols + right, value);
+        for n in 0 .. self.rows {
+            for m in 0 .. self.cols {
+                pass.set(top + n, left + m, self.get(n, m));
+            }
+        }
+        pass
+    }
+
+    /// Downsamples the matrix by keeping the maximum of every non-overlapping
+    /// `pool_h` by `pool_w` block. Trailing rows and columns that don't fill a whole
+    /// block are dropped, so the result is `(rows / pool_h, cols / pool_w)`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(4, 4, &[1, 2, 3, 4,
+    ///                              5, 6, 7, 8,
+    ///                              9, 1, 2, 3,
+    ///                              4, 5, 6, 0]);
+    ///
+    /// assert!(m.max_pool(2, 2) == matrix::from(2, 2, &[6, 8, 9, 6]));
+    /// assert!(m.max_pool(3, 3) == matrix::from(1, 1, &[9]));
+    ///
+    /// let big = matrix::from(1, 2, &[1u64 << 53, (1u64 << 53) + 1]);
+    /// assert!(big.max_pool(1, 2) == matrix::from(1, 1, &[(1u64 << 53) + 1]));
+    /// ```
+    pub fn max_pool(&self, pool_h: usize, pool_w: usize) -> Matrix<T> {
+        if pool_h == 0 || pool_w == 0 {
+            panic!("Pooling blocks must not be empty!")
+        }
+
+        let mut pass = Matrix::<T>::new(self.rows / pool_h, self.cols / pool_w, T::zero());
+        for n in 0 .. pass.rows {
+            for m in 0 .. pass.cols {
+                let mut max = self.get(n * pool_h, m * pool_w);
+                for a in n * pool_h .. (n + 1) * pool_h {
+                    for b in m * pool_w .. (m + 1) * pool_w {
+                        if self.get(a, b) > max {
+                            max = self.get(a, b)
+                        }
+                    }
+                }
+                pass.set(n, m, max);
+            }
+        }
+        pass
+    }
+
+    /// Downsamples the matrix by averaging every non-overlapping `pool_h` by `pool_w`
+    /// block, dropping incomplete blocks the same way as `max_pool`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(4, 4, &[1, 2, 3, 4,
+    ///                              5, 6, 7, 8,
+    ///                              9, 1, 2, 3,
+    ///                              4, 5, 6, 0]);
+    ///
+    /// assert!(m.average_pool(2, 2) == matrix::from(2, 2, &[3.5, 5.5, 4.75, 2.75]));
+    /// ```
+    pub fn average_pool(&self, pool_h: usize, pool_w: usize) -> Matrix<f64> {
+        if pool_h == 0 || pool_w == 0 {
+            panic!("Pooling blocks must not be empty!")
+        }
+
+        let mut pass = Matrix::<f64>::new(self.rows / pool_h, self.cols / pool_w, 0.0);
+        for n in 0 .. pass.rows {
+            for m in 0 .. pass.cols {
+                let mut sum = 0.0;
+                for a in n * pool_h .. (n + 1) * pool_h {
+                    for b in m * pool_w .. (m + 1) * pool_w {
+                        sum += self.get(a, b).to_f64();
+                    }
+                }
+                pass.set(n, m, sum / (pool_h * pool_w) as f64);
+            }
+        }
+        pass
+    }
+
+    /// Column index of the maximum of every row, the first one on ties. Decodes
+    /// one-hot or probability rows back into class indices. Panics when the matrix has
+    /// rows but no columns, since those rows have no maximum.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let probs = matrix::from(3, 3, &[0.1, 0.7, 0.2,
+    ///                                  0.8, 0.1, 0.1,
+    ///                                  0.2, 0.3, 0.5]);
+    /// assert_eq!(probs.argmax_rows(), vec![1, 0, 2]);
+    ///
+    /// let labels = [2, 0, 1];
+    /// assert_eq!(matrix::one_hot_rows(&labels, 3).argmax_rows(), labels.to_vec());
+    ///
+    /// let big = matrix::from(1, 2, &[1i64 << 53, (1i64 << 53) + 1]);
+    /// assert_eq!(big.argmax_rows(), vec![1]);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use lars::matrix::Matrix;
+    ///
+    /// Matrix::new(3, 0, 0.0).argmax_rows();
+    /// ```
+    pub fn argmax_rows(&self) -> Vec<usize> {
+        if self.rows > 0 && self.cols == 0 {
+            panic!("Can't take the maximum of rows without columns!")
+        }
+
+        let mut indices = Vec::with_capacity(self.rows);
+        for n in 0 .. self.rows {
+            let mut best = 0;
+            for m in 1 .. self.cols {
+                if self.get(n, m) > self.get(n, best) {
+                    best = m
+                }
+            }
+            indices.push(best);
+        }
+        indices
+    }
+
+    /// Picks `k` rows without replacement, in random order, e.g. to draw a minibatch.
+    /// The same `seed` always gives the same rows.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(5, 2, &[0, 0, 1, 10, 2, 20, 3, 30, 4, 40]);
+    /// let batch = m.sample_rows(3, 7);
+    ///
+    /// assert_eq!(batch.shape(), (3, 2));
+    /// for n in 0 .. 3 {
+    ///     assert_eq!(batch.get(n, 1), batch.get(n, 0) * 10);
+    ///     assert!(batch.get(n, 0) < 5);
+    /// }
+    /// assert!(batch == m.sample_rows(3, 7));
+    /// ```
+    pub fn sample_rows(&self, k: usize, seed: u64) -> Matrix<T> {
+        let rows: Vec<usize> = (0 .. self.rows).collect();
+        self.select_rows(vector::from(&rows).sample(k, seed).as_slice())
+    }
+
+    /// Shuffles the rows in place with a seeded Fisher-Yates shuffle, so the same
+    /// `seed` always gives the same permutation.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(4, 2, &[0, 0, 1, 10, 2, 20, 3, 30]);
+    ///
+    /// let mut a = m.clone();
+    /// let mut b = m.clone();
+    /// a.shuffle_rows(3);
+    /// b.shuffle_rows(3);
+    /// assert!(a == b);
+    ///
+    /// let mut firsts: Vec<i32> = (0 .. 4).map(|n| a.get(n, 0)).collect();
+    /// firsts.sort();
+    /// assert_eq!(firsts, vec![0, 1, 2, 3]);
+    /// for n in 0 .. 4 {
+    ///     assert_eq!(a.get(n, 1), a.get(n, 0) * 10);
+    /// }
+    /// ```
+    pub fn shuffle_rows(&mut self, seed: u64) {
+        let mut rng = vector::seeded_rng(seed);
+        for n in (1 .. self.rows).rev() {
+            let pick = rng.gen_range(0, n + 1);
+            self.swap_rows(n, pick);
+        }
+    }
+
+    /// Shuffles the rows with `seed` and splits them into `(train, test)` matrices, the
+    /// test part holding `test_fraction` of the rows, rounded to the nearest row.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(10, 1, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    /// let (train, test) = m.train_test_split(0.3, 1);
+    ///
+    /// assert_eq!(train.shape(), (7, 1));
+    /// assert_eq!(test.shape(), (3, 1));
+    ///
+    /// let mut rows: Vec<i32> = train.get_vector();
+    /// rows.extend(test.get_vector());
+    /// rows.sort();
+    /// assert_eq!(rows, m.get_vector());
+    /// ```
+    pub fn train_test_split(&self, test_fraction: f64, seed: u64) -> (Matrix<T>, Matrix<T>) {
+        if !(0.0 ..= 1.0).contains(&test_fraction) {
+            panic!("Test fraction must be between 0 and 1!")
+        }
+
+        let mut order: Vec<usize> = (0 .. self.rows).collect();
+        let mut rng = vector::seeded_rng(seed);
+        rng.shuffle(&mut order);
+
+        let test_rows = (self.rows as f64 * test_fraction).round() as usize;
+        (self.select_rows(&order[test_rows ..]), self.select_rows(&order[.. test_rows]))
+    }
+
+    fn select_rows(&self, indices: &[usize]) -> Matrix<T> {
+        let mut pass = Matrix::<T> {
+            rows: indices.len(),
+            cols: self.cols,
+            content: Vector::new(0, T::zero()),
+        };
+        for &n in indices {
+            let start = n * self.cols;
+            pass.content.as_mut_vec().extend_from_slice(&self.content.as_slice()[start .. start + self.cols]);
+        }
+        pass
+    }
+
+    pub fn powf(&self, pow: f64) {
+        self.content.powf(pow);
+    }
+
+    /// Combines two matrices of the same dimensions element-wise with `f`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(2, 2, &[1, 5, 3, 0]);
+    /// let b = matrix::from(2, 2, &[4, 2, 3, 1]);
+    ///
+    /// let max = a.zip_map(&b, |x, y| if x > y { x } else { y });
+    /// assert!(max == matrix::from(2, 2, &[4, 5, 3, 1]));
+    /// ```
+    pub fn zip_map(&self, other: &Matrix<T>, f: impl Fn(T, T) -> T) -> Matrix<T> {
+        if self.same_shape(other) {
+            let mut pass = self.clone();
+            for n in 0 .. self.content.len() {
+                pass.content[n] = f(self.content[n], other.content[n]);
+            }
+            pass
+        } else {
+            panic!("Can't zip matrices of different dimensions!")
+        }
+    }
+
+    /// Element-wise maximum of two matrices of the same dimensions.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let a = matrix::from(2, 2, &[1, 5, -3, 0]);
+    /// let b = matrix::from(2, 2, &[4, 2, -3, -1]);
+    ///
+    /// assert!(a.maximum(&b) == matrix::from(2, 2, &[4, 5, -3, 0]));
+    /// assert!(a.minimum(&b) == matrix::from(2, 2, &[1, 2, -3, -1]));
+    ///
+    /// let big = matrix::from(1, 2, &[1u64 << 53, (1u64 << 53) + 1]);
+    /// let bigger = matrix::from(1, 2, &[(1u64 << 53) + 1, 1u64 << 53]);
+    /// assert!(big.maximum(&bigger) == matrix::from(1, 2, &[(1u64 << 53) + 1; 2]));
+    /// assert!(big.minimum(&bigger) == matrix::from(1, 2, &[1u64 << 53; 2]));
+    /// ```
+    pub fn maximum(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.zip_map(other, |x, y| if y > x { y } else { x })
+    }
+
+    /// Element-wise minimum of two matrices of the same dimensions.
+    pub fn minimum(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.zip_map(other, |x, y| if y < x { y } else { x })
+    }
+
+    /// Encodes the matrix as its rows and cols (`u64`s) followed by the encoded content
+    /// vector, see `Vector::to_bytes`.
+    ///
+    /// ```
+    /// use lars::error::ShapeError;
+    /// use lars::matrix;
+    /// use lars::matrix::Matrix;
+    ///
+    /// let m = matrix::from(2, 3, &[1, -2, 3, -4, 5, -6]);
+    /// let bytes = m.to_bytes();
+    ///
+    /// assert!(Matrix::<i32>::from_bytes(&bytes).unwrap() == m);
+    /// assert!(Matrix::<i32>::from_bytes(&bytes[.. 10]).is_err());
+    ///
+    /// // rows * cols overflows, it must not wrap around to the content length.
+    /// let mut forged = bytes.clone();
+    /// forged[.. 8].copy_from_slice(&(1u64 << 63).to_le_bytes());
+    /// forged[8 .. 16].copy_from_slice(&2u64.to_le_bytes());
+    /// assert_eq!(Matrix::<i32>::from_bytes(&forged).err(),
+    ///            Some(ShapeError::LengthMismatch { expected: usize::MAX, found: 6 }));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> where T: LeBytes {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(self.rows as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.cols as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.content.to_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Matrix<T>, ShapeError> where T: LeBytes {
+        if bytes.len() < 16 {
+            return Err(ShapeError::LengthMismatch {
+                expected: 16,
+                found: bytes.len(),
+            })
+        }
+
+        let rows = u64::read_le(&bytes[.. 8]) as usize;
+        let cols = u64::read_le(&bytes[8 .. 16]) as usize;
+        let content = Vector::<T>::from_bytes(&bytes[16 ..])?;
+        let expected = rows.checked_mul(cols);
+        if expected != Some(content.len()) {
+            return Err(ShapeError::LengthMismatch {
+                expected: expected.unwrap_or(usize::MAX),
+                found: content.len(),
+            })
+        }
+
+        Ok(Matrix::<T> {
+            rows,
+            cols,
+            content,
+        })
+    }
+
+    /// Approximates the dominant eigenvalue and its unit eigenvector by repeatedly
+    /// multiplying and normalizing a start vector. Returns `None` for non-square
+    /// matrices or when the iteration collapses to the zero vector.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// // Eigenvalues are (5 ± √5) / 2
+    /// let m = matrix::from(2, 2, &[2.0, 1.0, 1.0, 3.0]);
+    /// let (value, v) = m.power_iteration(100).unwrap();
+    ///
+    /// assert!((value - (5.0 + 5f64.sqrt()) / 2.0).abs() < 1e-9);
+    /// assert!((v[1] / v[0] - (1.0 + 5f64.sqrt()) / 2.0).abs() < 1e-9);
+    /// ```
+    pub fn power_iteration(&self, iterations: usize) -> Option<(f64, Vector<f64>)> {
+        if self.rows != self.cols || self.rows == 0 {
+            return None
+        }
+
+        let a = self.to_f64();
+        let mut v = Vector::<f64>::new(self.rows, 1.0 / (self.rows as f64).sqrt());
+        for _ in 0 .. iterations {
+            let w = &a * &v;
+            let mut norm = 0.0;
+            for n in 0 .. w.len() {
+                norm += w[n] * w[n];
+            }
+            if norm == 0.0 {
+                return None
+            }
+            v = w / norm.sqrt();
+        }
+
+        let w = &a * &v;
+        let mut value = 0.0;
+        for n in 0 .. v.len() {
+            value += v[n] * w[n];
+        }
+        Some((value, v))
+    }
+
+    /// Returns the symmetric part `(A + Aᵀ) / 2` of a square matrix.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(2, 2, &[1.0, 2.0, 5.0, 4.0]);
+    /// let sym = m.symmetric_part();
+    /// let skew = m.skew_part();
+    ///
+    /// assert!(sym == matrix::from(2, 2, &[1.0, 3.5, 3.5, 4.0]));
+    /// assert!(skew == matrix::from(2, 2, &[0.0, -1.5, 1.5, 0.0]));
+    /// assert!(sym + skew == m);
+    /// ```
+    pub fn symmetric_part(&self) -> Matrix<f64> {
+        self.to_f64().symmetrize()
+    }
+
+    /// Returns `(A + Aᵀ) / 2` of a square matrix in its own element type, e.g. to
+    /// remove the asymmetry rounding leaves in a computed covariance matrix. Integer
+    /// elements are halved with truncation.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let noisy = matrix::from(3, 3, &[2.0f64, 1.0 + 1e-9, 0.5,
+    ///                                  1.0, 3.0, -0.25 - 1e-12,
+    ///                                  0.5 + 1e-10, -0.25, 1.0]);
+    /// let m = noisy.symmetrize();
+    ///
+    /// assert!(m == m.transposed());
+    /// assert!((m.get(0, 1) - (1.0 + 0.5e-9)).abs() < 1e-15);
+    /// ```
+    pub fn symmetrize(&self) -> Matrix<T> {
+        if self.rows == self.cols {
+            (self.clone() + self.transposed()) / (T::one() + T::one())
+        } else {
+            panic!("Matrix must be a square!")
+        }
+    }
+
+    /// Returns the skew-symmetric part `(A - Aᵀ) / 2` of a square matrix.
+    pub fn skew_part(&self) -> Matrix<f64> {
+        if self.rows == self.cols {
+            let a = self.to_f64();
+            (a.clone() - a.transposed()) / 2.0
+        } else {
+            panic!("Matrix must be a square!")
+        }
+    }
+
+    /// Population covariance matrix of the columns, treating every row as one
+    /// observation of `cols` variables.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let data = matrix::from(3, 2, &[1.0, 2.0,
+    ///                                 2.0, 4.0,
+    ///                                 3.0, 3.0]);
+    /// let cov = data.covariance();
+    ///
+    /// assert!((cov.get(0, 0) - 2.0 / 3.0).abs() < 1e-12);
+    /// assert!((cov.get(0, 1) - 1.0 / 3.0).abs() < 1e-12);
+    /// assert!(cov == cov.transposed());
+    /// ```
+    pub fn covariance(&self) -> Matrix<f64> {
+        let mut means = vec![0.0; self.cols];
+        for (m, mean) in means.iter_mut().enumerate() {
+            for n in 0 .. self.rows {
+                *mean += self.get(n, m).to_f64();
+            }
+            *mean /= self.rows as f64;
+        }
+
+        let mut pass = Matrix::<f64>::new(self.cols, self.cols, 0.0);
+        for a in 0 .. self.cols {
+            for b in a .. self.cols {
+                let mut sum = 0.0;
+                for n in 0 .. self.rows {
+                    sum += (self.get(n, a).to_f64() - means[a]) * (self.get(n, b).to_f64() - means[b]);
+                }
+                pass.set(a, b, sum / self.rows as f64);
+                pass.set(b, a, sum / self.rows as f64);
+            }
+        }
+        pass
+    }
+
+    /// Pearson correlation matrix of the columns, the covariance normalized by the
+    /// product of standard deviations. Like `Vector::pearson_correlation`, a constant
+    /// column has no defined correlation, so its whole row and column are NaN,
+    /// diagonal included.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let data = matrix::from(4, 3, &[1.0, 2.0, 0.5,
+    ///                                 2.0, 4.0, 0.1,
+    ///                                 3.0, 3.0, 0.9,
+    ///                                 4.0, 7.0, 0.2]);
+    /// let corr = data.correlation_matrix();
+    ///
+    /// for n in 0 .. 3 {
+    ///     assert!((corr.get(n, n) - 1.0).abs() < 1e-12);
+    /// }
+    /// assert!(corr == corr.transposed());
+    ///
+    /// let constant = matrix::from(3, 2, &[1.0, 5.0, 2.0, 5.0, 3.0, 5.0]);
+    /// let corr = constant.correlation_matrix();
+    ///
+    /// assert!((corr.get(0, 0) - 1.0).abs() < 1e-12);
+    /// assert!(corr.get(0, 1).is_nan() && corr.get(1, 0).is_nan());
+    /// assert!(corr.get(1, 1).is_nan());
+    /// ```
+    pub fn correlation_matrix(&self) -> Matrix<f64> {
+        let mut pass = self.covariance();
+        let mut std_devs = Vec::with_capacity(self.cols);
+        for n in 0 .. self.cols {
+            std_devs.push(pass.get(n, n).sqrt());
+        }
+        for a in 0 .. self.cols {
+            for b in 0 .. self.cols {
+                let c = pass.get(a, b) / (std_devs[a] * std_devs[b]);
+                pass.set(a, b, c);
+            }
+        }
+        pass
+    }
+
+    /// Batch normalization: shifts and scales every column to zero mean and unit
+    /// variance, `(x - μ) / √(σ² + eps)`, treating the rows as samples. `eps` keeps
+    /// constant columns from dividing by zero; they come out as all zeros.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(3, 2, &[1.0, 5.0,
+    ///                              2.0, 5.0,
+    ///                              6.0, 5.0]);
+    /// let norm = m.batch_norm(1e-8);
+    ///
+    /// for c in 0 .. 2 {
+    ///     let mean = (norm.get(0, c) + norm.get(1, c) + norm.get(2, c)) / 3.0;
+    ///     assert!(mean.abs() < 1e-12);
+    /// }
+    /// let var = (0 .. 3).map(|n| norm.get(n, 0) * norm.get(n, 0)).sum::<f64>() / 3.0;
+    /// assert!((var - 1.0).abs() < 1e-6);
+    /// assert_eq!(norm.get(0, 1), 0.0);
+    /// ```
+    pub fn batch_norm(&self, eps: f64) -> Matrix<f64> {
+        let mut pass = self.to_f64();
+        for m in 0 .. self.cols {
+            let mut mean = 0.0;
+            for n in 0 .. self.rows {
+                mean += pass.get(n, m);
+            }
+            mean /= self.rows as f64;
+
+            let mut variance = 0.0;
+            for n in 0 .. self.rows {
+                variance += (pass.get(n, m) - mean) * (pass.get(n, m) - mean);
+            }
+            variance /= self.rows as f64;
+
+            let scale = (variance + eps).sqrt();
+            for n in 0 .. self.rows {
+                let x = (pass.get(n, m) - mean) / scale;
+                pass.set(n, m, x);
+            }
+        }
+        pass
+    }
+
+    /// Converts every element to `f64`, e.g. to feed an integer matrix to float algorithms.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(2, 2, &[1, -2, 3, 40]);
+    ///
+    /// assert!(m.to_f64() == matrix::from(2, 2, &[1.0, -2.0, 3.0, 40.0]));
+    /// assert!(m.to_f32() == matrix::from(2, 2, &[1.0f32, -2.0, 3.0, 40.0]));
+    /// ```
+    pub fn to_f64(&self) -> Matrix<f64> {
+        Matrix::<f64> {
+            rows: self.rows,
+            cols: self.cols,
+            content: self.content.to_f64(),
+        }
+    }
+
+    /// Converts every element to `f32`.
+    pub fn to_f32(&self) -> Matrix<f32> {
+        Matrix::<f32> {
+            rows: self.rows,
+            cols: self.cols,
+            content: self.content.to_f32(),
+        }
+    }
+
+    /// Rounds every element to the nearest integer, half-way cases away from zero.
+    ///
+    /// ```
+    /// use lars::matrix;
+    ///
+    /// let m = matrix::from(1, 3, &[1.4, 1.6, -1.5]);
+    ///
+    /// assert!(m.round() == matrix::from(1, 3, &[1.0, 2.0, -2.0]));
+    /// assert!(m.floor() == matrix::from(1, 3, &[1.0, 1.0, -2.0]));
+    /// assert!(m.ceil() == matrix::from(1, 3, &[2.0, 2.0, -1.0]));
+    /// ```
+    pub fn round(&self) -> Matrix<f64> {
+        Matrix::<f64> {
+            rows: self.rows,
+            cols: self.cols,
+            content: self.content.round(),
+        }
+    }
+
+    pub fn floor(&self) -> Matrix<f64> {
+        Matrix::<f64> {
+            rows: self.rows,
+            cols: self.cols,
+            content: self.content.floor(),
+        }
+    }
+
+    pub fn ceil(&self) -> Matrix<f64> {
+        Matrix::<f64> {
+            rows: self.rows,
+            cols: self.cols,
+            content: self.content.ceil(),
+        }
+    }
+
+    /// Applies the activation function to every element, or to every row for `Softmax`.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::matrix::Activation;
+    ///
+    /// let m = matrix::from(2, 2, &[0.0, -1.0, 2.0, 1.0]);
+    ///
+    /// assert!(m.apply_activation(Activation::Identity) == m);
+    /// assert!(m.apply_activation(Activation::Relu) == matrix::from(2, 2, &[0.0, 0.0, 2.0, 1.0]));
+    ///
+    /// let sigmoid = m.apply_activation(Activation::Sigmoid);
+    /// assert_eq!(sigmoid.get(0, 0), 0.5);
+    /// assert!((sigmoid.get(1, 1) - 1.0 / (1.0 + (-1.0f64).exp())).abs() < 1e-12);
+    ///
+    /// let tanh = m.apply_activation(Activation::Tanh);
+    /// assert!((tanh.get(1, 0) - 2.0f64.tanh()).abs() < 1e-12);
+    ///
+    /// let softmax = m.apply_activation(Activation::Softmax);
+    /// for n in 0 .. 2 {
+    ///     assert!((softmax.get(n, 0) + softmax.get(n, 1) - 1.0).abs() < 1e-12);
+    /// }
+    /// assert!((softmax.get(0, 0) - 1.0 / (1.0 + (-1.0f64).exp())).abs() < 1e-12);
+    /// ```
+    pub fn apply_activation(&self, act: Activation) -> Matrix<f64> {
+        let mut pass = self.to_f64();
+        match act {
+            Activation::Sigmoid => pass.map_in_place(|x| 1.0 / (1.0 + (-x).exp())),
+            Activation::Relu => pass.map_in_place(|x| x.max(0.0)),
+            Activation::Tanh => pass.map_in_place(f64::tanh),
+            Activation::Identity => (),
+            Activation::Softmax => {
+                for n in 0 .. pass.rows {
+                    let mut max = f64::NEG_INFINITY;
+                    for m in 0 .. pass.cols {
+                        max = max.max(pass.get(n, m));
+                    }
+                    let mut sum = 0.0;
+                    for m in 0 .. pass.cols {
+                        let e = (pass.get(n, m) - max).exp();
+                        pass.set(n, m, e);
+                        sum += e;
+                    }
+                    for m in 0 .. pass.cols {
+                        let p = pass.get(n, m) / sum;
+                        pass.set(n, m, p);
+                    }
+                }
+            }
+        }
+        pass
+    }
+}
+
+impl Matrix<f64> {
+    fn map_in_place(&mut self, f: impl Fn(f64) -> f64) {
+        for x in self.content.iter_mut() {
+            *x = f(*x)
+        }
+    }
+}
+
+/// Wraps a matrix and memoizes its determinant until the matrix is modified through
+/// `set`, for algorithms that query the determinant of an unchanged matrix repeatedly.
+///
+/// ```
+/// use lars::matrix;
+/// use lars::matrix::CachedMatrix;
+///
+/// let mut m = CachedMatrix::new(matrix::from(2, 2, &[3.0, 1.0, 2.0, 4.0]));
+///
+/// assert_eq!(m.determinant(), 10.0);
+/// assert_eq!(m.determinant(), 10.0);
+///
+/// m.set(0, 0, 1.0);
+/// assert_eq!(m.determinant(), 2.0);
+/// assert!(m.into_inner() == matrix::from(2, 2, &[1.0, 1.0, 2.0, 4.0]));
+/// ```
+pub struct CachedMatrix<T: Number> {
+    matrix: Matrix<T>,
+    determinant: Cell<Option<f64>>,
+}
+
+impl<T: Number> CachedMatrix<T> {
+    pub fn new(matrix: Matrix<T>) -> CachedMatrix<T> {
+        CachedMatrix {
+            matrix,
+            determinant: Cell::new(None),
+        }
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> T {
+        self.matrix.get(r, c)
+    }
+
+    /// Sets an element, invalidating the cached determinant.
+    pub fn set(&mut self, r: usize, c: usize, a: T) {
+        self.matrix.set(r, c, a);
+        self.determinant.set(None);
+    }
+
+    pub fn determinant(&self) -> f64 {
+        match self.determinant.get() {
+            Some(det) => det,
+            None => {
+                let det = self.matrix.determinant();
+                self.determinant.set(Some(det));
+                det
+            }
+        }
+    }
+
+    pub fn matrix(&self) -> &Matrix<T> {
+        &self.matrix
+    }
+
+    pub fn into_inner(self) -> Matrix<T> {
+        self.matrix
+    }
+}
+
+pub fn from<T: Number>(rows: usize, cols: usize, elements: &[T]) -> Matrix<T> {
+    Matrix::<T> {
+        rows: rows,
+        cols: cols,
+        content: vector::from(elements),
+    }
+}
+
+/// Stacks equally long vectors as the rows of a matrix.
+///
+/// ```
+/// use lars::matrix;
+/// use lars::vector;
+///
+/// let rows = [vector::from(&[1, 2, 3]), vector::from(&[4, 5, 6])];
+///
+/// let m = matrix::from_rows(&rows);
+/// assert_eq!((m.get_rows(), m.get_cols()), (2, 3));
+/// assert_eq!(m.get(1, 0), 4);
+///
+/// let m = matrix::from_cols(&rows);
+/// assert_eq!((m.get_rows(), m.get_cols()), (3, 2));
+/// assert_eq!(m.get(0, 1), 4);
+/// ```
+pub fn from_rows<T: Number>(rows: &[Vector<T>]) -> Matrix<T> {
+    let cols = if rows.is_empty() { 0 } else { rows[0].len() };
+    let mut content = Vector::<T>::new(0, T::zero());
+    content.as_mut_vec().reserve(rows.len() * cols);
+    for row in rows {
+        if row.len() != cols {
+            panic!("Can't stack vectors of different dimensions!")
+        }
+        content.as_mut_vec().extend_from_slice(row.as_slice());
+    }
+    Matrix::<T> {
+        rows: rows.len(),
+        cols,
+        content,
+    }
+}
+
+/// Stacks equally long vectors as the columns of a matrix.
+pub fn from_cols<T: Number>(cols: &[Vector<T>]) -> Matrix<T> {
+    from_rows(cols).transposed()
+}
+
+/// Places the given matrices along the diagonal of a zero matrix.
+///
+/// ```
+/// use lars::matrix;
+///
+/// let a = matrix::from(2, 2, &[1, 2, 3, 4]);
+/// let b = matrix::from(1, 1, &[5]);
+///
+/// let m = matrix::block_diagonal(&[a, b]);
+/// assert!(m == matrix::from(3, 3, &[1, 2, 0,
+///                                   3, 4, 0,
+///                                   0, 0, 5]));
+/// ```
+pub fn block_diagonal<T: Number>(blocks: &[Matrix<T>]) -> Matrix<T> {
+    let mut rows = 0;
+    let mut cols = 0;
+    for block in blocks {
+        rows += block.rows;
+        cols += block.cols;
+    }
+
+    let mut pass = zeros(rows, cols);
+    let mut r = 0;
+    let mut c = 0;
+    for block in blocks {
+        for n in 0 .. block.rows {
+            for m in 0 .. block.cols {
+                pass.set(r + n, c + m, block.get(n, m));
+            }
+        }
+        r += block.rows;
+        c += block.cols;
+    }
+    pass
+}
+
+/// Pairwise RBF (Gaussian) kernel `exp(-gamma·‖xᵢ - xⱼ‖²)` between the rows of `data`.
+///
+/// ```
+/// use lars::matrix;
+///
+/// let data = matrix::from(3, 2, &[0.0, 0.0, 1.0, 0.0, 0.0, 2.0]);
+/// let k = matrix::rbf_kernel(&data, 0.5);
+///
+/// assert_eq!((k.get_rows(), k.get_cols()), (3, 3));
+/// for n in 0 .. 3 {
+///     assert_eq!(k.get(n, n), 1.0);
+/// }
+/// assert!(k == k.transposed());
+/// assert_eq!(k.get(0, 1), (-0.5f64).exp());
+/// ```
+pub fn rbf_kernel(data: &Matrix<f64>, gamma: f64) -> Matrix<f64> {
+    let mut pass = Matrix::<f64>::new(data.rows, data.rows, 1.0);
+    for n in 0 .. data.rows {
+        for m in n + 1 .. data.rows {
+            let mut dist = 0.0;
+            for k in 0 .. data.cols {
+                let d = data.get(n, k) - data.get(m, k);
+                dist += d * d;
+            }
+            let similarity = (-gamma * dist).exp();
+            pass.set(n, m, similarity);
+            pass.set(m, n, similarity);
+        }
+    }
+    pass
+}
+
+/// Volume of the parallelepiped spanned by `n` vectors of dimension `n`, the absolute
+/// determinant of the matrix having them as rows.
+///
+/// ```
+/// use lars::matrix;
+/// use lars::vector;
+///
+/// let basis = [
+///     vector::from(&[1.0, 0.0, 0.0]),
+///     vector::from(&[0.0, 1.0, 0.0]),
+///     vector::from(&[0.0, 0.0, 1.0]),
+/// ];
+/// assert_eq!(matrix::parallelepiped_volume(&basis), 1.0);
+///
+/// let skewed = [vector::from(&[2.0, 1.0]), vector::from(&[1.0, 3.0])];
+/// assert!((matrix::parallelepiped_volume(&skewed) - 5.0).abs() < 1e-12);
+/// ```
+pub fn parallelepiped_volume(vectors: &[Vector<f64>]) -> f64 {
+    from_rows(vectors).determinant().abs()
+}
+
+pub fn identity<T: Number>(size: usize) -> Matrix<T> {
+    let mut i = Matrix::<T>::new(size, size, T::zero());
+    for n in 0 .. size {
+        i.set(n, n, T::one());
+    }
+    i
+}
+
+/// Square matrix with the given elements on its main diagonal and zeros elsewhere.
+pub fn diagonal<T: Number>(diag: &Vector<T>) -> Matrix<T> {
+    let mut d = Matrix::<T>::new(diag.len(), diag.len(), T::zero());
+    for n in 0 .. diag.len() {
+        d.set(n, n, diag[n]);
+    }
+    d
+}
+
+/// Vandermonde matrix with `degree + 1` columns, column `j` holding `xᵢʲ`.
+///
+/// ```
+/// use lars::matrix;
+/// use lars::vector;
+///
+/// let v = matrix::vandermonde(&vector::from(&[1.0, 2.0, 3.0]), 2);
+///
+/// assert!(v == matrix::from(3, 3, &[1.0, 1.0, 1.0,
+///                                   1.0, 2.0, 4.0,
+///                                   1.0, 3.0, 9.0]));
+/// ```
+pub fn vandermonde(x: &Vector<f64>, degree: usize) -> Matrix<f64> {
+    let mut pass = Matrix::<f64>::new(x.len(), degree + 1, 1.0);
+    for n in 0 .. x.len() {
+        for m in 1 .. degree + 1 {
+            let power = pass.get(n, m - 1) * x[n];
+            pass.set(n, m, power);
+        }
+    }
+    pass
+}
+
+/// Hilbert matrix of size `n`, `(i, j) = 1 / (i + j + 1)`. A classic ill-conditioned
+/// test matrix for solvers.
+///
+/// ```
+/// use lars::matrix;
+/// use lars::matrix::Matrix;
+///
+/// let h = matrix::hilbert(2);
+/// assert!(h == matrix::from(2, 2, &[1.0, 0.5, 0.5, 1.0 / 3.0]));
+///
+/// // Condition number in the infinity norm, ‖H‖·‖H⁻¹‖
+/// let inf_norm = |m: &Matrix<f64>| (0 .. m.get_rows())
+///     .map(|n| (0 .. m.get_cols()).map(|k| m.get(n, k).abs()).sum::<f64>())
+///     .fold(0.0, f64::max);
+/// let cond = |n| {
+///     let h = matrix::hilbert(n);
+///     inf_norm(&h) * inf_norm(&h.inverse().unwrap())
+/// };
+///
+/// assert!((cond(2) - 27.0).abs() < 1e-9);
+/// assert!(cond(6) > 1e7);
+/// ```
+pub fn hilbert(n: usize) -> Matrix<f64> {
+    let mut pass = Matrix::<f64>::new(n, n, 0.0);
+    for i in 0 .. n {
+        for j in 0 .. n {
+            pass.set(i, j, 1.0 / (i + j + 1) as f64);
+        }
+    }
+    pass
+}
+
+/// Toeplitz matrix, constant along every descending diagonal, with the given first
+/// column and first row. The corner element is taken from `first_col`.
+///
+/// ```
+/// use lars::matrix;
+/// use lars::vector;
+///
+/// let t = matrix::toeplitz(&vector::from(&[1, 4, 5]), &vector::from(&[1, 2, 3]));
+///
+/// assert!(t == matrix::from(3, 3, &[1, 2, 3,
+///                                   4, 1, 2,
+///                                   5, 4, 1]));
+/// for n in 1 .. 3 {
+///     for m in 1 .. 3 {
+///         assert_eq!(t.get(n, m), t.get(n - 1, m - 1));
+///     }
+/// }
+/// ```
+pub fn toeplitz<T: Number>(first_col: &Vector<T>, first_row: &Vector<T>) -> Matrix<T> {
+    let mut pass = Matrix::<T>::new(first_col.len(), first_row.len(), T::zero());
+    for n in 0 .. first_col.len() {
+        for m in 0 .. first_row.len() {
+            pass.set(n, m, if n >= m { first_col[n - m] } else { first_row[m - n] });
+        }
+    }
+    pass
+}
+
+/// Square circulant matrix whose every row is the previous one rotated right by one.
+///
+/// ```
+/// use lars::matrix;
+/// use lars::vector;
+///
+/// let c = matrix::circulant(&vector::from(&[1, 2, 3]));
+///
+/// assert!(c == matrix::from(3, 3, &[1, 2, 3,
+///                                   3, 1, 2,
+///                                   2, 3, 1]));
+/// ```
+pub fn circulant<T: Number>(first_row: &Vector<T>) -> Matrix<T> {
+    let size = first_row.len();
+    let mut pass = Matrix::<T>::new(size, size, T::zero());
+    for n in 0 .. size {
+        for m in 0 .. size {
+            pass.set(n, m, first_row[(m + size - n) % size]);
+        }
+    }
+    pass
+}
+
+/// Tridiagonal matrix with `diag` on the main diagonal and `sub`/`sup` directly
+/// below/above it. For an `n` by `n` matrix the lengths must be `n - 1`, `n` and `n - 1`.
+///
+/// ```
+/// use lars::matrix;
+///
+/// let m = matrix::tridiagonal(&[1, 2], &[3, 4, 5], &[6, 7]);
+///
+/// assert!(m == matrix::from(3, 3, &[3, 6, 0,
+///                                   1, 4, 7,
+///                                   0, 2, 5]));
+/// ```
+pub fn tridiagonal<T: Number>(sub: &[T], diag: &[T], sup: &[T]) -> Matrix<T> {
+    let n = diag.len();
+    if n == 0 || sub.len() != n - 1 || sup.len() != n - 1 {
+        panic!("Off-diagonals must be one element shorter than the diagonal!")
+    }
+
+    let mut pass = Matrix::<T>::new(n, n, T::zero());
+    for (k, &d) in diag.iter().enumerate() {
+        pass.set(k, k, d);
+    }
+    for k in 0 .. n - 1 {
+        pass.set(k + 1, k, sub[k]);
+        pass.set(k, k + 1, sup[k]);
+    }
+    pass
+}
+
+/// Solves the tridiagonal system `tridiagonal(sub, diag, sup) · x = rhs` in O(n) with
+/// the Thomas algorithm. Returns `None` on mismatched lengths or a zero pivot; the
+/// algorithm doesn't pivot, so it's meant for diagonally dominant systems.
+///
+/// ```
+/// use lars::matrix;
+/// use lars::vector;
+///
+/// let (sub, diag, sup) = ([1.0, 1.0], [4.0, 4.0, 4.0], [1.0, 1.0]);
+/// let x = vector::from(&[1.0, 2.0, 3.0]);
+/// let rhs = matrix::tridiagonal(&sub, &diag, &sup) * x.clone();
+///
+/// let solved = matrix::solve_tridiagonal(&sub, &diag, &sup, &rhs).unwrap();
+/// for n in 0 .. 3 {
+///     assert!((solved[n] - x[n]).abs() < 1e-12);
+/// }
+/// assert!(matrix::solve_tridiagonal(&sub, &diag, &sup, &vector::from(&[1.0])).is_none());
+/// ```
+pub fn solve_tridiagonal(sub: &[f64], diag: &[f64], sup: &[f64], rhs: &Vector<f64>) -> Option<Vector<f64>> {
+    let n = diag.len();
+    if n == 0 || sub.len() != n - 1 || sup.len() != n - 1 || rhs.len() != n {
+        return None
+    }
+
+    let mut c = vec![0.0; n];
+    let mut d = vec![0.0; n];
+    let mut pivot = diag[0];
+    for k in 0 .. n {
+        if k > 0 {
+            pivot = diag[k] - sub[k - 1] * c[k - 1];
+        }
+        if pivot == 0.0 {
+            return None
+        }
+        if k < n - 1 {
+            c[k] = sup[k] / pivot;
+        }
+        d[k] = if k > 0 { (rhs[k] - sub[k - 1] * d[k - 1]) / pivot } else { rhs[k] / pivot };
+    }
+
+    for k in (0 .. n - 1).rev() {
+        d[k] -= c[k] * d[k + 1];
+    }
+    Some(vector::from(&d))
+}
+
+/// Stacks the one-hot encodings of `labels` as rows of a
+/// `labels.len()` by `num_classes` matrix.
+///
+/// ```
+/// use lars::matrix;
+///
+/// let labels = [2, 0, 1, 2];
+/// let m = matrix::one_hot_rows(&labels, 3);
+///
+/// assert_eq!(m.shape(), (4, 3));
+/// for (n, &label) in labels.iter().enumerate() {
+///     assert_eq!(m.get(n, 0) + m.get(n, 1) + m.get(n, 2), 1.0);
+///     assert_eq!(m.get(n, label), 1.0);
+/// }
+/// ```
+pub fn one_hot_rows(labels: &[usize], num_classes: usize) -> Matrix<f64> {
+    let mut pass = Matrix::<f64>::new(labels.len(), num_classes, 0.0);
+    for (n, &label) in labels.iter().enumerate() {
+        if label >= num_classes {
+            panic!("Label {} out of bounds for {} classes!", label, num_classes)
+        }
+        pass.set(n, label, 1.0);
+    }
+    pass
+}
+
+pub fn zeros_like<T: Number>(other: Matrix<T>) -> Matrix<T> {
+    Matrix::<T> {
+        rows: other.rows,
+        cols: other.cols,
+        content: Vector::new(other.rows * other.cols, T::zero()),
+    }
+}
+
+pub fn zeros<T: Number>(rows: usize, cols: usize) -> Matrix<T> {
+    Matrix::<T> {
+        rows: rows,
+        cols: cols,
+        content: Vector::new(rows * cols, T::zero()),
+    }
+}
+
+/// Xavier (Glorot) initialization for a `rows` inputs by `cols` outputs weight matrix,
+/// drawn from a seeded normal distribution with variance `2 / (rows + cols)`.
+///
+/// ```
+/// use lars::matrix;
+///
+/// let w = matrix::xavier(200, 300, 5);
+/// let v = w.to_vector();
+///
+/// assert!(v.mean().abs() < 0.01);
+/// assert!((v.variance() / (2.0 / 500.0) - 1.0).abs() < 0.05);
+/// assert!(w == matrix::xavier(200, 300, 5));
+/// ```
+pub fn xavier(rows: usize, cols: usize, seed: u64) -> Matrix<f64> {
+    gaussian(rows, cols, (2.0 / (rows + cols) as f64).sqrt(), seed)
+}
+
+/// He initialization for a `rows` inputs by `cols` outputs weight matrix, drawn from a
+/// seeded normal distribution with variance `2 / rows`. Suits ReLU layers.
+///
+/// ```
+/// use lars::matrix;
+///
+/// let v = matrix::he(200, 300, 5).to_vector();
+///
+/// assert!((v.variance() / (2.0 / 200.0) - 1.0).abs() < 0.05);
+/// ```
+pub fn he(rows: usize, cols: usize, seed: u64) -> Matrix<f64> {
+    gaussian(rows, cols, (2.0 / rows as f64).sqrt(), seed)
+}
+
+/// Normally distributed matrix with zero mean, using the Box-Muller transform.
+fn gaussian(rows: usize, cols: usize, std_dev: f64, seed: u64) -> Matrix<f64> {
+    let mut rng = vector::seeded_rng(seed);
+    let mut pass = Matrix::<f64>::new(rows, cols, 0.0);
+    for x in pass.content.iter_mut() {
+        let u = 1.0 - rng.gen::<f64>();
+        let v = rng.gen::<f64>();
+        *x = std_dev * (-2.0 * u.ln()).sqrt() * (2.0 * ::std::f64::consts::PI * v).cos();
     }
+    pass
 }
 
 pub fn random<T: Number + rand::Rand>(rows: usize, cols: usize) -> Matrix<T> {
@@ -0,0 +1,53 @@
+//! Minimal neural network building blocks on top of the matrix type.
+
+use matrix::Matrix;
+use vector::Vector;
+
+/// Fully connected layer mapping `inputs` features to `outputs` features.
+pub struct Dense {
+    /// `inputs` by `outputs` weight matrix.
+    pub weights: Matrix<f64>,
+    /// One bias per output.
+    pub bias: Vector<f64>,
+}
+
+impl Dense {
+    pub fn new(weights: Matrix<f64>, bias: Vector<f64>) -> Dense {
+        if weights.get_cols() != bias.len() {
+            panic!("Bias must have one element per output!")
+        }
+        Dense {
+            weights,
+            bias,
+        }
+    }
+
+    /// Computes `input · W + b` for a batch with one sample per row, adding the bias
+    /// to every row.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::nn::Dense;
+    /// use lars::vector;
+    ///
+    /// let layer = Dense::new(
+    ///     matrix::from(3, 2, &[1.0, 0.0, 0.0, 1.0, 1.0, -1.0]),
+    ///     vector::from(&[0.5, -0.5]),
+    /// );
+    /// let input = matrix::from(2, 3, &[1.0, 2.0, 3.0, 0.0, 0.0, 1.0]);
+    /// let output = layer.forward(&input);
+    ///
+    /// assert_eq!(output.shape(), (2, 2));
+    /// assert!(output == matrix::from(2, 2, &[4.5, -1.5, 1.5, -1.5]));
+    /// ```
+    pub fn forward(&self, input: &Matrix<f64>) -> Matrix<f64> {
+        let mut output = input.mul_ref(&self.weights);
+        for n in 0 .. output.get_rows() {
+            for m in 0 .. output.get_cols() {
+                let x = output.get(n, m) + self.bias[m];
+                output.set(n, m, x);
+            }
+        }
+        output
+    }
+}
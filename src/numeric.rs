@@ -0,0 +1,32 @@
+//! Numerical approximations built on top of the vector type.
+
+use vector::Vector;
+
+/// Approximates the gradient of `f` at `x` with central differences,
+/// `(f(x + eps·eᵢ) - f(x - eps·eᵢ)) / 2eps`.
+///
+/// ```
+/// use lars::numeric;
+/// use lars::vector;
+///
+/// let x = vector::from(&[1.0, -2.0, 0.5]);
+/// let grad = numeric::gradient(|v| v.dot(v), &x, 1e-6);
+///
+/// for n in 0 .. x.len() {
+///     assert!((grad[n] - 2.0 * x[n]).abs() < 1e-6);
+/// }
+/// ```
+pub fn gradient(f: impl Fn(&Vector<f64>) -> f64, x: &Vector<f64>, eps: f64) -> Vector<f64> {
+    let mut grad = Vector::new(x.len(), 0.0);
+    let mut probe = x.clone();
+    for n in 0 .. x.len() {
+        probe[n] = x[n] + eps;
+        let forward = f(&probe);
+        probe[n] = x[n] - eps;
+        let backward = f(&probe);
+        probe[n] = x[n];
+
+        grad[n] = (forward - backward) / (2.0 * eps);
+    }
+    grad
+}
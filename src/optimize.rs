@@ -0,0 +1,36 @@
+//! Simple first-order optimizers working on vectors.
+
+use vector::Vector;
+
+/// Runs `steps` iterations of plain gradient descent, `x ← x - lr·∇f(x)`, starting
+/// from `x0` and returning the final point.
+///
+/// ```
+/// use lars::numeric;
+/// use lars::optimize;
+/// use lars::vector;
+///
+/// // Quadratic bowl with its minimum at (3, -1).
+/// let f = |v: &vector::Vector<f64>| (v[0] - 3.0).powi(2) + 2.0 * (v[1] + 1.0).powi(2);
+/// let grad = |v: &vector::Vector<f64>| vector::from(&[2.0 * (v[0] - 3.0), 4.0 * (v[1] + 1.0)]);
+///
+/// let x = optimize::gradient_descent(grad, vector::from(&[0.0, 0.0]), 0.1, 200);
+/// assert!((x[0] - 3.0).abs() < 1e-6);
+/// assert!((x[1] + 1.0).abs() < 1e-6);
+///
+/// let x = optimize::gradient_descent(|v| numeric::gradient(f, v, 1e-6), vector::from(&[0.0, 0.0]), 0.1, 200);
+/// assert!((x[0] - 3.0).abs() < 1e-4);
+/// ```
+pub fn gradient_descent(grad: impl Fn(&Vector<f64>) -> Vector<f64>, x0: Vector<f64>, lr: f64, steps: usize) -> Vector<f64> {
+    let mut x = x0;
+    for _ in 0 .. steps {
+        let g = grad(&x);
+        if g.len() != x.len() {
+            panic!("Gradient must have the same dimension as the point!")
+        }
+        for n in 0 .. x.len() {
+            x[n] -= lr * g[n];
+        }
+    }
+    x
+}
@@ -0,0 +1,61 @@
+//! Least-squares fitting on top of the matrix type.
+
+use matrix;
+use matrix::Matrix;
+use vector::Vector;
+
+/// Fits the coefficients `β` of `y ≈ X·β` by solving the normal equations
+/// `XᵀX·β = Xᵀy`. Returns `None` when the lengths don't match or `XᵀX` is singular,
+/// e.g. for linearly dependent columns. Add a column of ones to `x` to fit an intercept.
+///
+/// ```
+/// use lars::matrix;
+/// use lars::regression;
+/// use lars::vector;
+///
+/// // y = 2·x₀ - 3·x₁
+/// let x = matrix::from(4, 2, &[1.0, 0.0,
+///                              0.0, 1.0,
+///                              1.0, 1.0,
+///                              2.0, 1.0]);
+/// let y = vector::from(&[2.0, -3.0, -1.0, 1.0]);
+///
+/// let beta = regression::linear_fit(&x, &y).unwrap();
+/// assert!((beta[0] - 2.0).abs() < 1e-12);
+/// assert!((beta[1] + 3.0).abs() < 1e-12);
+/// ```
+pub fn linear_fit(x: &Matrix<f64>, y: &Vector<f64>) -> Option<Vector<f64>> {
+    if x.get_rows() != y.len() {
+        return None
+    }
+
+    let xtx = x.transpose_mul(x);
+    let xty = x.transpose_mul(&y.to_matrix(y.len(), 1)).to_vector();
+    xtx.solve(&xty)
+}
+
+/// Fits the coefficients of a polynomial of the given degree through the points
+/// `(xᵢ, yᵢ)` in the least-squares sense, constant term first so the result can be
+/// evaluated with `eval_poly`. Returns `None` when the lengths don't match or there
+/// are fewer distinct points than coefficients.
+///
+/// ```
+/// use lars::regression;
+/// use lars::vector;
+///
+/// // y = 1 - 2x + 0.5x²
+/// let x = vector::from(&[-2.0, -1.0, 0.0, 1.0, 2.0, 3.0]);
+/// let mut y = x.clone();
+/// for n in 0 .. x.len() {
+///     y[n] = 1.0 - 2.0 * x[n] + 0.5 * x[n] * x[n];
+/// }
+///
+/// let p = regression::polyfit(&x, &y, 2).unwrap();
+/// assert!((p[0] - 1.0).abs() < 1e-9);
+/// assert!((p[1] + 2.0).abs() < 1e-9);
+/// assert!((p[2] - 0.5).abs() < 1e-9);
+/// assert!((p.eval_poly(4.0) - 1.0).abs() < 1e-9);
+/// ```
+pub fn polyfit(x: &Vector<f64>, y: &Vector<f64>, degree: usize) -> Option<Vector<f64>> {
+    linear_fit(&matrix::vandermonde(x, degree), y)
+}
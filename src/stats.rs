@@ -0,0 +1,80 @@
+//! Streaming statistics for data that doesn't fit in a single vector.
+
+use common::Number;
+use vector::Vector;
+
+/// Running mean and population variance, updated one sample at a time with Welford's
+/// numerically stable algorithm.
+///
+/// ```
+/// use lars::stats::OnlineStats;
+/// use lars::vector;
+///
+/// let v = vector::from(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+///
+/// let mut stats = OnlineStats::new();
+/// for n in 0 .. v.len() {
+///     stats.push(v[n]);
+/// }
+/// assert_eq!(stats.count(), 8);
+/// assert!((stats.mean() - v.mean()).abs() < 1e-12);
+/// assert!((stats.variance() - v.variance()).abs() < 1e-12);
+///
+/// let stats = OnlineStats::from_vector(&vector::from(&[1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0]));
+/// assert!((stats.variance() - 22.5).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OnlineStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl OnlineStats {
+    pub fn new() -> OnlineStats {
+        OnlineStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    pub fn from_vector<T: Number>(v: &Vector<T>) -> OnlineStats {
+        let mut stats = OnlineStats::new();
+        for n in 0 .. v.len() {
+            stats.push(v[n].to_f64());
+        }
+        stats
+    }
+
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Mean of the samples so far, NaN before the first sample like `Vector::mean`.
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.mean
+        }
+    }
+
+    /// Population variance of the samples so far, NaN before the first sample.
+    pub fn variance(&self) -> f64 {
+        self.m2 / self.count as f64
+    }
+}
+
+impl Default for OnlineStats {
+    fn default() -> OnlineStats {
+        OnlineStats::new()
+    }
+}
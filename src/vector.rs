@@ -67,15 +67,20 @@
 
 extern crate rand;
 
+use self::rand::{Rng, SeedableRng};
+
 use std::fmt;
+use std::slice;
 
 use std::ops::{Index, IndexMut, Add, Sub, Mul, Div, Neg};
-use common::Number;
+use common::{LeBytes, Number};
+use error::ShapeError;
 
+use matrix;
 use matrix::Matrix;
 
 pub struct Vector<T: Number> {
-    pub content: Vec<T>,
+    content: Vec<T>,
 }
 
 impl<T: Number + fmt::Display> fmt::Display for Vector<T> {
@@ -122,45 +127,21 @@ impl<T: Number> Clone for Vector<T> {
 impl<T: Number> Add<Vector<T>> for Vector<T> {
     type Output = Vector<T>;
     fn add(self, rhs: Vector<T>) -> Vector<T> {
-        if self.len() == rhs.len() {
-            let mut pass = Vector::<T>::new(self.len(), T::zero());
-            for n in 0 .. self.len() {
-                pass.content[n] = self.content[n] + rhs.content[n];
-            }
-            pass
-        } else {
-            panic!("Trying to add vectors of different dimensions!")
-        }
+        self.add_ref(&rhs)
     }
 }
 
 impl<T: Number> Sub<Vector<T>> for Vector<T> {
     type Output = Vector<T>;
     fn sub(self, rhs: Vector<T>) -> Vector<T> {
-        if self.len() == rhs.len() {
-            let mut pass = Vector::<T>::new(self.len(), T::zero());
-            for n in 0 .. self.len() {
-                pass.content[n] = self.content[n] - rhs.content[n];
-            }
-            pass
-        } else {
-            panic!("Trying to subtract vectors of different dimensions!")
-        }
+        self.sub_ref(&rhs)
     }
 }
 
 impl<T: Number> Mul<Vector<T>> for Vector<T> {
     type Output = Vector<T>;
     fn mul(self, rhs: Vector<T>) -> Vector<T> {
-        if self.len() == rhs.len() {
-            let mut pass = Vector::<T>::new(self.len(), T::zero());
-            for n in 0 .. self.len() {
-                pass.content[n] = self.content[n] * rhs.content[n];
-            }
-            pass
-        } else {
-            panic!("Trying to multiply vectors of different dimensions!")
-        }
+        self.mul_ref(&rhs)
     }
 }
 
@@ -255,15 +236,7 @@ impl<T: Number> Sub<Matrix<T>> for Vector<T> {
 impl<T: Number> Div<Vector<T>> for Vector<T> {
     type Output = Vector<T>;
     fn div(self, rhs: Vector<T>) -> Vector<T> {
-        if self.len() == rhs.len() {
-            let mut pass = Vector::<T>::new(self.len(), T::zero());
-            for n in 0 .. self.len() {
-                pass.content[n] = self.content[n] / rhs.content[n];
-            }
-            pass
-        } else {
-            panic!("Trying to divide vectors of different dimensions!")
-        }
+        self.div_ref(&rhs)
     }
 }
 
@@ -322,6 +295,96 @@ impl<T: Number> Sub<T> for Vector<T> {
     }
 }
 
+/// Borrowing counterpart of `Add<Vector<T>>`, leaving both operands usable.
+///
+/// ```
+/// use lars::vector;
+///
+/// let a = vector::from(&[1.0, 2.0, 3.0]);
+/// let b = vector::from(&[4.0, 5.0, 6.0]);
+///
+/// assert!(&a + &b == a.clone() + b.clone());
+/// assert!(&a - &b == a.clone() - b.clone());
+/// assert!(&a * &b == a.clone() * b.clone());
+/// assert!(&a / &b == a.clone() / b.clone());
+/// assert!(&a * 2.0 == a.clone() * 2.0);
+///
+/// // Neither a nor b were moved
+/// assert_eq!(a.len() + b.len(), 6);
+/// ```
+impl<T: Number> Add<&Vector<T>> for &Vector<T> {
+    type Output = Vector<T>;
+    fn add(self, rhs: &Vector<T>) -> Vector<T> {
+        self.add_ref(rhs)
+    }
+}
+
+impl<T: Number> Sub<&Vector<T>> for &Vector<T> {
+    type Output = Vector<T>;
+    fn sub(self, rhs: &Vector<T>) -> Vector<T> {
+        self.sub_ref(rhs)
+    }
+}
+
+impl<T: Number> Mul<&Vector<T>> for &Vector<T> {
+    type Output = Vector<T>;
+    fn mul(self, rhs: &Vector<T>) -> Vector<T> {
+        self.mul_ref(rhs)
+    }
+}
+
+impl<T: Number> Div<&Vector<T>> for &Vector<T> {
+    type Output = Vector<T>;
+    fn div(self, rhs: &Vector<T>) -> Vector<T> {
+        self.div_ref(rhs)
+    }
+}
+
+impl<T: Number> Add<T> for &Vector<T> {
+    type Output = Vector<T>;
+    fn add(self, rhs: T) -> Vector<T> {
+        let mut v = self.clone();
+        for x in v.content.iter_mut() {
+            *x = *x + rhs
+        }
+        v
+    }
+}
+
+impl<T: Number> Sub<T> for &Vector<T> {
+    type Output = Vector<T>;
+    fn sub(self, rhs: T) -> Vector<T> {
+        let mut v = self.clone();
+        for x in v.content.iter_mut() {
+            *x = *x - rhs
+        }
+        v
+    }
+}
+
+impl<T: Number> Mul<T> for &Vector<T> {
+    type Output = Vector<T>;
+    fn mul(self, rhs: T) -> Vector<T> {
+        let mut v = self.clone();
+        v.scale_mut(rhs);
+        v
+    }
+}
+
+impl<T: Number> Div<T> for &Vector<T> {
+    type Output = Vector<T>;
+    fn div(self, rhs: T) -> Vector<T> {
+        let mut v = self.clone();
+        for x in v.content.iter_mut() {
+            *x = *x / rhs
+        }
+        v
+    }
+}
+
+/// Element-wise `==`, so float vectors follow IEEE 754: `-0.0` equals `0.0`, but a
+/// vector containing NaN isn't equal to anything, not even itself. Use `total_eq`
+/// when NaN should compare equal to NaN.
 impl<T: Number> PartialEq for Vector<T> {
     fn eq(&self, other: &Vector<T>) -> bool {
         if self.len() != other.len() {
@@ -344,6 +407,125 @@ impl<T: Number> Vector<T> {
         self.content.len()
     }
 
+    /// Element at `index`, or `None` when it is out of bounds, where indexing with
+    /// `v[index]` would panic.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let mut v = vector::from(&[1.0, 2.0, 3.0]);
+    ///
+    /// assert_eq!(v.get(1), Some(&2.0));
+    /// assert_eq!(v.get(3), None);
+    ///
+    /// if let Some(x) = v.get_mut(2) {
+    ///     *x = 6.0;
+    /// }
+    /// assert!(v.get_mut(3).is_none());
+    /// assert!(v == vector::from(&[1.0, 2.0, 6.0]));
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.content.get(index)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.content.get_mut(index)
+    }
+
+    /// Iterates over the elements in order.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let mut v = vector::from(&[1, 2, 3]);
+    ///
+    /// assert_eq!(v.iter().sum::<i32>(), 6);
+    ///
+    /// for x in v.iter_mut() {
+    ///     *x *= 10;
+    /// }
+    /// assert!(v == vector::from(&[10, 20, 30]));
+    /// ```
+    #[inline]
+    pub fn iter<'a>(&'a self) -> slice::Iter<'a, T> {
+        self.content.iter()
+    }
+
+    #[inline]
+    pub fn iter_mut<'a>(&'a mut self) -> slice::IterMut<'a, T> {
+        self.content.iter_mut()
+    }
+
+    /// Borrows the elements as a slice.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let mut v = vector::from(&[3.0, 1.0, 2.0]);
+    ///
+    /// assert_eq!(v.as_slice(), &[3.0, 1.0, 2.0]);
+    ///
+    /// v.as_mut_slice().swap(0, 2);
+    /// assert_eq!(v.as_slice(), &[2.0, 1.0, 3.0]);
+    /// ```
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.content
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.content
+    }
+
+    /// Appends an element, growing the vector by one.
+    ///
+    /// ```
+    /// use lars::vector::Vector;
+    ///
+    /// let mut v = Vector::new(0, 0.0);
+    /// v.push(1.5);
+    /// v.push(2.5);
+    ///
+    /// assert_eq!(v.len(), 2);
+    /// assert_eq!(v.as_slice(), &[1.5, 2.5]);
+    /// ```
+    #[inline]
+    pub fn push(&mut self, x: T) {
+        self.content.push(x)
+    }
+
+    /// Backing storage, for the matrix code that inserts and removes whole rows and
+    /// columns in place.
+    #[inline]
+    pub(crate) fn as_mut_vec(&mut self) -> &mut Vec<T> {
+        &mut self.content
+    }
+
+    /// Checks whether the vector has no elements. Sums over empty vectors, like `dot`
+    /// and the norms, are zero, while `mean` and `variance` are NaN.
+    ///
+    /// ```
+    /// use lars::vector;
+    /// use lars::vector::Vector;
+    ///
+    /// let empty = Vector::<f64>::new(0, 0.0);
+    ///
+    /// assert!(empty.is_empty());
+    /// assert!(!vector::from(&[1.0]).is_empty());
+    /// assert_eq!(empty.dot(&empty), 0.0);
+    /// assert_eq!(empty.norm(), 0.0);
+    /// assert_eq!(empty.inf_norm(), 0.0);
+    /// assert!(empty.mean().is_nan());
+    /// assert_eq!(format!("{}", empty), "[]");
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
     pub fn new(length: usize, default: T) -> Vector<T> {
         let d = vec![default; length];
         Vector::<T> {
@@ -351,11 +533,925 @@ impl<T: Number> Vector<T> {
         }
     }
 
+    /// Element-wise equality where NaN equals NaN and `-0.0` equals `0.0`, unlike
+    /// `==` which never considers a vector containing NaN equal.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let a = vector::from(&[1.0, ::std::f64::NAN, 0.0]);
+    /// let b = vector::from(&[1.0, ::std::f64::NAN, -0.0]);
+    ///
+    /// assert!(a != a);
+    /// assert!(a.total_eq(&a));
+    /// assert!(a.total_eq(&b));
+    /// assert!(!a.total_eq(&vector::from(&[1.0, 2.0, 0.0])));
+    ///
+    /// // Integers are compared exactly, even where f64 can't tell them apart.
+    /// let big = vector::from(&[1i64 << 53, u32::max_value() as i64]);
+    /// assert!(big.total_eq(&big));
+    /// assert!(!big.total_eq(&vector::from(&[(1i64 << 53) + 1, u32::max_value() as i64])));
+    /// assert!(!vector::from(&[u64::max_value()]).total_eq(&vector::from(&[u64::max_value() - 1])));
+    /// ```
+    pub fn total_eq(&self, other: &Vector<T>) -> bool {
+        if self.len() != other.len() {
+            return false
+        }
+        for n in 0 .. self.len() {
+            let (a, b) = (self.content[n], other.content[n]);
+            if !(a == b || (is_nan(a) && is_nan(b))) {
+                return false
+            }
+        }
+        true
+    }
+
     pub fn powf(&self, pow: f64) {
         for n in 0 .. self.len() {
             self.content[n].powf(pow);
         }
     }
+
+    /// Element-wise sum that borrows both operands, so neither has to be cloned.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let a = vector::from(&[1.0, 2.0, 3.0]);
+    /// let b = vector::from(&[4.0, 5.0, 6.0]);
+    ///
+    /// let sum = a.add_ref(&b);
+    /// let product = a.mul_ref(&b);
+    ///
+    /// // Both operands are still usable
+    /// assert!(sum == a.clone() + b.clone());
+    /// assert!(product == a * b);
+    /// ```
+    pub fn add_ref(&self, rhs: &Vector<T>) -> Vector<T> {
+        if self.len() == rhs.len() {
+            let mut pass = Vector::<T>::new(self.len(), T::zero());
+            for n in 0 .. self.len() {
+                pass.content[n] = self.content[n] + rhs.content[n];
+            }
+            pass
+        } else {
+            panic!("Trying to add vectors of different dimensions!")
+        }
+    }
+
+    pub fn sub_ref(&self, rhs: &Vector<T>) -> Vector<T> {
+        if self.len() == rhs.len() {
+            let mut pass = Vector::<T>::new(self.len(), T::zero());
+            for n in 0 .. self.len() {
+                pass.content[n] = self.content[n] - rhs.content[n];
+            }
+            pass
+        } else {
+            panic!("Trying to subtract vectors of different dimensions!")
+        }
+    }
+
+    pub fn mul_ref(&self, rhs: &Vector<T>) -> Vector<T> {
+        if self.len() == rhs.len() {
+            let mut pass = Vector::<T>::new(self.len(), T::zero());
+            for n in 0 .. self.len() {
+                pass.content[n] = self.content[n] * rhs.content[n];
+            }
+            pass
+        } else {
+            panic!("Trying to multiply vectors of different dimensions!")
+        }
+    }
+
+    pub fn div_ref(&self, rhs: &Vector<T>) -> Vector<T> {
+        if self.len() == rhs.len() {
+            let mut pass = Vector::<T>::new(self.len(), T::zero());
+            for n in 0 .. self.len() {
+                pass.content[n] = self.content[n] / rhs.content[n];
+            }
+            pass
+        } else {
+            panic!("Trying to divide vectors of different dimensions!")
+        }
+    }
+
+    /// Reverses the order of the elements in place.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let mut v = vector::from(&[1, 2, 3]);
+    /// v.reverse();
+    ///
+    /// assert!(v == vector::from(&[3, 2, 1]));
+    /// ```
+    pub fn reverse(&mut self) {
+        self.content.reverse()
+    }
+
+    /// Returns the vector multiplied by `factor`, same as `v * factor`.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let mut v = vector::from(&[1.0, -2.0, 3.0]);
+    /// assert!(v.scale(2.0) == v.clone() * 2.0);
+    ///
+    /// v.scale_mut(2.0);
+    /// assert!(v == vector::from(&[2.0, -4.0, 6.0]));
+    /// ```
+    pub fn scale(&self, factor: T) -> Vector<T> {
+        self.clone() * factor
+    }
+
+    /// Multiplies every element by `factor` in place.
+    pub fn scale_mut(&mut self, factor: T) {
+        for x in self.content.iter_mut() {
+            *x = *x * factor
+        }
+    }
+
+    /// Checks whether every element satisfies `pred`.
+    pub fn all(&self, pred: impl Fn(T) -> bool) -> bool {
+        self.content.iter().all(|x| pred(*x))
+    }
+
+    /// Checks whether at least one element satisfies `pred`.
+    pub fn any(&self, pred: impl Fn(T) -> bool) -> bool {
+        self.content.iter().any(|x| pred(*x))
+    }
+
+    /// Counts the elements satisfying `pred`.
+    pub fn count(&self, pred: impl Fn(T) -> bool) -> usize {
+        self.content.iter().filter(|x| pred(**x)).count()
+    }
+
+    /// Returns the positions of the nonzero elements.
+    pub fn nonzero_indices(&self) -> Vec<usize> {
+        let mut indices = Vec::new();
+        for n in 0 .. self.len() {
+            if self.content[n] != T::zero() {
+                indices.push(n);
+            }
+        }
+        indices
+    }
+
+    /// Fraction of the elements that are zero, `0.0` for an empty vector.
+    pub fn sparsity(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0
+        }
+        self.count(|x| x == T::zero()) as f64 / self.len() as f64
+    }
+
+    /// Inserts `value` at `idx`, shifting all following elements to the right.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let mut v = vector::from(&[1, 2, 4]);
+    ///
+    /// v.insert(2, 3);
+    /// assert!(v == vector::from(&[1, 2, 3, 4]));
+    ///
+    /// assert_eq!(v.remove(3), 4);
+    /// assert_eq!(v.len(), 3);
+    /// ```
+    pub fn insert(&mut self, idx: usize, value: T) {
+        if idx <= self.len() {
+            self.content.insert(idx, value)
+        } else {
+            panic!("Vector index {} out of bounds!", idx)
+        }
+    }
+
+    /// Removes and returns the element at `idx`, shifting all following elements to the left.
+    pub fn remove(&mut self, idx: usize) -> T {
+        if idx < self.len() {
+            self.content.remove(idx)
+        } else {
+            panic!("Vector index {} out of bounds!", idx)
+        }
+    }
+
+    /// Picks `k` elements without replacement, in random order. The same `seed`
+    /// always gives the same sample.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[10, 20, 30, 40, 50, 60]);
+    /// let s = v.sample(4, 42);
+    ///
+    /// assert_eq!(s.len(), 4);
+    /// assert!(s.all(|x| v.any(|y| y == x)));
+    /// assert!(s.all(|x| s.count(|y| y == x) == 1));
+    /// assert!(s == v.sample(4, 42));
+    /// ```
+    pub fn sample(&self, k: usize, seed: u64) -> Vector<T> {
+        if k > self.len() {
+            panic!("Can't sample {} elements from a vector of {}!", k, self.len())
+        }
+
+        let mut rng = seeded_rng(seed);
+        let mut indices: Vec<usize> = (0 .. self.len()).collect();
+        let mut pass = Vector::<T> {
+            content: Vec::with_capacity(k)
+        };
+        for n in 0 .. k {
+            let pick = rng.gen_range(n, indices.len());
+            indices.swap(n, pick);
+            pass.content.push(self.content[indices[n]]);
+        }
+        pass
+    }
+
+    /// Inverted dropout: zeros every element with probability `rate` and scales the
+    /// survivors by `1 / (1 - rate)`, keeping the expected value unchanged. The same
+    /// `seed` always drops the same elements.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1.0, 2.0, 3.0, 4.0]);
+    ///
+    /// assert!(v.dropout(0.0, 9) == v);
+    /// assert!(v.dropout(1.0, 9) == vector::from(&[0.0, 0.0, 0.0, 0.0]));
+    ///
+    /// let d = v.dropout(0.5, 9);
+    /// assert!(d == v.dropout(0.5, 9));
+    /// for n in 0 .. v.len() {
+    ///     assert!(d[n] == 0.0 || d[n] == 2.0 * v[n]);
+    /// }
+    /// ```
+    pub fn dropout(&self, rate: f64, seed: u64) -> Vector<f64> {
+        if !(0.0 ..= 1.0).contains(&rate) {
+            panic!("Dropout rate must be between 0 and 1!")
+        }
+
+        let mut rng = seeded_rng(seed);
+        let mut pass = self.to_f64();
+        for x in pass.content.iter_mut() {
+            if rng.gen::<f64>() < rate {
+                *x = 0.0
+            } else {
+                *x /= 1.0 - rate
+            }
+        }
+        pass
+    }
+
+    /// Wraps the elements as a row-major `rows` by `cols` matrix.
+    ///
+    /// ```
+    /// use lars::matrix;
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1, 2, 3, 4, 5, 6]);
+    ///
+    /// assert!(v.to_matrix(2, 3) == matrix::from(2, 3, &[1, 2, 3, 4, 5, 6]));
+    /// assert_eq!(v.to_matrix(3, 2).get(2, 0), 5);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use lars::vector;
+    ///
+    /// vector::from(&[1, 2, 3, 4, 5, 6]).to_matrix(4, 2);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use lars::vector;
+    ///
+    /// // 2^63 * 2 wraps to 0 elements, it must not be accepted.
+    /// vector::from(&[0i32; 0]).to_matrix(1 << 63, 2);
+    /// ```
+    pub fn to_matrix(&self, rows: usize, cols: usize) -> Matrix<T> {
+        if rows.checked_mul(cols) == Some(self.len()) {
+            matrix::from(rows, cols, &self.content)
+        } else {
+            panic!("Can't fit {} elements into a {}x{} matrix!", self.len(), rows, cols)
+        }
+    }
+
+    /// Dot product accumulated in `T`, so small integer types wrap (or panic in
+    /// debug builds) on overflow. See `dot_wide` for a widening alternative.
+    pub fn dot(&self, other: &Vector<T>) -> T {
+        if self.len() == other.len() {
+            let mut sum = T::zero();
+            for n in 0 .. self.len() {
+                sum = sum + self.content[n] * other.content[n];
+            }
+            sum
+        } else {
+            panic!("Trying to dot vectors of different dimensions!")
+        }
+    }
+
+    /// Dot product accumulated in `f64`, avoiding overflow for small integer types.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let a = vector::from(&[100i8, 100, 100]);
+    /// let b = vector::from(&[2i8, 2, 2]);
+    ///
+    /// // 600 doesn't fit in an i8
+    /// assert_eq!(a.dot_wide(&b), 600.0);
+    /// ```
+    pub fn dot_wide(&self, other: &Vector<T>) -> f64 {
+        if self.len() == other.len() {
+            let mut sum = 0.0;
+            for n in 0 .. self.len() {
+                sum += self.content[n].to_f64() * other.content[n].to_f64();
+            }
+            sum
+        } else {
+            panic!("Trying to dot vectors of different dimensions!")
+        }
+    }
+
+    /// Euclidean length of the vector.
+    pub fn norm(&self) -> f64 {
+        self.p_norm(2.0)
+    }
+
+    /// Computes `Σxᵢ²`, the squared Euclidean norm without the square root.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1.0, -2.0, 3.0]);
+    ///
+    /// assert_eq!(v.sum_of_squares(), 14.0);
+    /// assert!((v.sum_of_squares() - v.norm() * v.norm()).abs() < 1e-12);
+    /// ```
+    pub fn sum_of_squares(&self) -> f64 {
+        self.dot_wide(self)
+    }
+
+    /// Computes `(Σ|xᵢ|^p)^(1/p)`. Panics unless `p >= 1`, the range where this is a
+    /// norm; NaN is rejected too.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[-3.0, 4.0]);
+    ///
+    /// assert_eq!(v.l1_norm(), 7.0);
+    /// assert_eq!(v.p_norm(2.0), 5.0);
+    /// assert_eq!(v.norm(), 5.0);
+    /// assert_eq!(v.inf_norm(), 4.0);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use lars::vector;
+    ///
+    /// vector::from(&[-3.0, 4.0]).p_norm(0.5);
+    /// ```
+    pub fn p_norm(&self, p: f64) -> f64 {
+        if p.is_nan() || p < 1.0 {
+            panic!("Norm order must be at least 1, got {}!", p)
+        }
+
+        let mut sum = 0.0;
+        for n in 0 .. self.len() {
+            sum += self.content[n].to_f64().abs().powf(p);
+        }
+        sum.powf(1.0 / p)
+    }
+
+    /// Computes `Σ|xᵢ|`.
+    pub fn l1_norm(&self) -> f64 {
+        let mut sum = 0.0;
+        for n in 0 .. self.len() {
+            sum += self.content[n].to_f64().abs();
+        }
+        sum
+    }
+
+    /// Computes `max|xᵢ|`, the infinity norm.
+    pub fn inf_norm(&self) -> f64 {
+        let mut max = 0.0;
+        for n in 0 .. self.len() {
+            let x = self.content[n].to_f64().abs();
+            if x > max {
+                max = x
+            }
+        }
+        max
+    }
+
+    /// Scales the vector down to a norm of `max_norm` if its norm exceeds it.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[3.0, 4.0]);
+    ///
+    /// assert_eq!(v.clamp_norm(10.0).norm(), 5.0);
+    /// assert!((v.clamp_norm(2.5).norm() - 2.5).abs() < 1e-12);
+    /// ```
+    pub fn clamp_norm(&self, max_norm: f64) -> Vector<f64> {
+        let v = self.to_f64();
+        let norm = v.norm();
+        if norm > max_norm {
+            v * (max_norm / norm)
+        } else {
+            v
+        }
+    }
+
+    /// Divides every element by the total so the result sums to 1, e.g. to turn
+    /// counts into a probability distribution. Vectors summing to zero are returned
+    /// unchanged.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let p = vector::from(&[1, 3, 4]).normalize_to_sum();
+    ///
+    /// assert!(p == vector::from(&[0.125, 0.375, 0.5]));
+    /// assert_eq!(p.iter().sum::<f64>(), 1.0);
+    /// assert!(vector::from(&[0, 0]).normalize_to_sum() == vector::from(&[0.0, 0.0]));
+    /// ```
+    pub fn normalize_to_sum(&self) -> Vector<f64> {
+        let v = self.to_f64();
+        let sum: f64 = v.content.iter().sum();
+        if sum == 0.0 {
+            v
+        } else {
+            v / sum
+        }
+    }
+
+    /// Converts every element to `f64`.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1, -2, 3]);
+    ///
+    /// assert!(v.to_f64() == vector::from(&[1.0, -2.0, 3.0]));
+    /// assert!(v.to_f32() == vector::from(&[1.0f32, -2.0, 3.0]));
+    /// ```
+    pub fn to_f64(&self) -> Vector<f64> {
+        let mut pass = Vector::<f64> {
+            content: Vec::with_capacity(self.len())
+        };
+        for n in 0 .. self.len() {
+            pass.content.push(self.content[n].to_f64());
+        }
+        pass
+    }
+
+    /// Formats the vector like `Display`, but with every element rounded to `digits`
+    /// decimal places.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1.23456, -0.5]);
+    ///
+    /// assert!(v.to_string_precision(2).contains("1.23"));
+    /// assert_eq!(v.to_string_precision(2), "[1.23,-0.50]");
+    /// ```
+    pub fn to_string_precision(&self, digits: usize) -> String {
+        let mut cells = Vec::with_capacity(self.len());
+        for n in 0 .. self.len() {
+            cells.push(format!("{:.*}", digits, self.content[n].to_f64()));
+        }
+        format!("[{}]", cells.join(","))
+    }
+
+    /// Converts every element to `f32`.
+    pub fn to_f32(&self) -> Vector<f32> {
+        let mut pass = Vector::<f32> {
+            content: Vec::with_capacity(self.len())
+        };
+        for n in 0 .. self.len() {
+            pass.content.push(self.content[n].to_f64() as f32);
+        }
+        pass
+    }
+
+    /// Rounds every element to the nearest integer, half-way cases away from zero.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1.4, 1.6, -1.5]);
+    ///
+    /// assert!(v.round() == vector::from(&[1.0, 2.0, -2.0]));
+    /// assert!(v.floor() == vector::from(&[1.0, 1.0, -2.0]));
+    /// assert!(v.ceil() == vector::from(&[2.0, 2.0, -1.0]));
+    /// ```
+    pub fn round(&self) -> Vector<f64> {
+        self.map_f64(f64::round)
+    }
+
+    pub fn floor(&self) -> Vector<f64> {
+        self.map_f64(f64::floor)
+    }
+
+    pub fn ceil(&self) -> Vector<f64> {
+        self.map_f64(f64::ceil)
+    }
+
+    fn map_f64(&self, f: impl Fn(f64) -> f64) -> Vector<f64> {
+        let mut pass = self.to_f64();
+        for x in pass.content.iter_mut() {
+            *x = f(*x)
+        }
+        pass
+    }
+
+    pub fn mean(&self) -> f64 {
+        let mut sum = 0.0;
+        for n in 0 .. self.len() {
+            sum += self.content[n].to_f64();
+        }
+        sum / self.len() as f64
+    }
+
+    /// Population variance, `Σ(xᵢ - x̄)² / n`.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// assert_eq!(vector::from(&[2, 4, 4, 4, 5, 5, 7, 9]).variance(), 4.0);
+    /// ```
+    pub fn variance(&self) -> f64 {
+        let mean = self.mean();
+        let mut sum = 0.0;
+        for n in 0 .. self.len() {
+            let d = self.content[n].to_f64() - mean;
+            sum += d * d;
+        }
+        sum / self.len() as f64
+    }
+
+    /// Pearson correlation coefficient between two equally long vectors, in `[-1, 1]`.
+    /// Constant vectors have no defined correlation and give NaN.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let x = vector::from(&[1.0, 2.0, 3.0, 4.0]);
+    ///
+    /// assert!((x.pearson_correlation(&vector::from(&[3.0, 5.0, 7.0, 9.0])) - 1.0).abs() < 1e-12);
+    /// assert!((x.pearson_correlation(&vector::from(&[4.0, 3.0, 2.0, 1.0])) + 1.0).abs() < 1e-12);
+    /// ```
+    pub fn pearson_correlation(&self, other: &Vector<T>) -> f64 {
+        if self.len() != other.len() {
+            panic!("Trying to correlate vectors of different dimensions!")
+        }
+
+        let (mean_x, mean_y) = (self.mean(), other.mean());
+        let mut covariance = 0.0;
+        for n in 0 .. self.len() {
+            covariance += (self.content[n].to_f64() - mean_x) * (other.content[n].to_f64() - mean_y);
+        }
+        covariance / self.len() as f64 / (self.variance() * other.variance()).sqrt()
+    }
+
+    /// Simple moving average over a sliding window, of length `len - window + 1`.
+    /// Windows longer than the vector give an empty result.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1, 2, 3, 4]);
+    ///
+    /// assert!(v.moving_average(2) == vector::from(&[1.5, 2.5, 3.5]));
+    /// assert!(v.moving_average(4) == vector::from(&[2.5]));
+    /// assert_eq!(v.moving_average(5).len(), 0);
+    /// ```
+    pub fn moving_average(&self, window: usize) -> Vector<f64> {
+        if window == 0 {
+            panic!("Moving average window must not be empty!")
+        }
+        if window > self.len() {
+            return Vector::new(0, 0.0)
+        }
+
+        let mut pass = Vector::<f64> {
+            content: Vec::with_capacity(self.len() - window + 1)
+        };
+        let mut sum = 0.0;
+        for n in 0 .. self.len() {
+            sum += self.content[n].to_f64();
+            if n >= window {
+                sum -= self.content[n - window].to_f64();
+            }
+            if n + 1 >= window {
+                pass.content.push(sum / window as f64);
+            }
+        }
+        pass
+    }
+
+    /// Discrete 1D convolution in full mode, of length `n + k - 1`.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1, 2, 3]);
+    ///
+    /// assert!(v.convolve(&vector::from(&[1, 1])) == vector::from(&[1, 3, 5, 3]));
+    /// assert!(v.convolve(&vector::from(&[1])) == v);
+    /// ```
+    pub fn convolve(&self, kernel: &Vector<T>) -> Vector<T> {
+        if self.is_empty() || kernel.is_empty() {
+            return Vector::new(0, T::zero())
+        }
+
+        let mut pass = Vector::new(self.len() + kernel.len() - 1, T::zero());
+        for n in 0 .. self.len() {
+            for k in 0 .. kernel.len() {
+                pass.content[n + k] = pass.content[n + k] + self.content[n] * kernel.content[k];
+            }
+        }
+        pass
+    }
+
+    /// Discrete Fourier transform `Xₖ = Σ xₙ·e^(-2πikn/N)` by the naive O(n²) sum,
+    /// returned as its real and imaginary parts.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let (re, im) = vector::from(&[2.0, 2.0, 2.0, 2.0]).dft();
+    ///
+    /// assert!((re[0] - 8.0).abs() < 1e-12);
+    /// for k in 1 .. 4 {
+    ///     assert!(re[k].abs() < 1e-12);
+    /// }
+    /// assert!(im.inf_norm() < 1e-12);
+    /// ```
+    pub fn dft(&self) -> (Vector<f64>, Vector<f64>) {
+        let size = self.len();
+        let mut re = Vector::<f64>::new(size, 0.0);
+        let mut im = Vector::<f64>::new(size, 0.0);
+        for k in 0 .. size {
+            for n in 0 .. size {
+                let angle = -2.0 * ::std::f64::consts::PI * ((k * n) % size) as f64 / size as f64;
+                let x = self.content[n].to_f64();
+                re[k] += x * angle.cos();
+                im[k] += x * angle.sin();
+            }
+        }
+        (re, im)
+    }
+
+    /// Same transform as `dft`, computed in O(n log n) with the iterative radix-2
+    /// Cooley-Tukey algorithm when the length is a power of two and falling back to
+    /// `dft` otherwise.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1.0, -2.0, 0.5, 3.0, 0.0, 1.5, -1.0, 2.0]);
+    /// let (re, im) = v.fft();
+    /// let (dft_re, dft_im) = v.dft();
+    ///
+    /// assert!((re - dft_re).inf_norm() < 1e-12);
+    /// assert!((im - dft_im).inf_norm() < 1e-12);
+    /// ```
+    pub fn fft(&self) -> (Vector<f64>, Vector<f64>) {
+        let size = self.len();
+        if !size.is_power_of_two() {
+            return self.dft()
+        }
+
+        let mut re = self.to_f64();
+        let mut im = Vector::<f64>::new(size, 0.0);
+        let mut j = 0;
+        for i in 1 .. size {
+            let mut bit = size >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j |= bit;
+            if i < j {
+                re.content.swap(i, j);
+            }
+        }
+
+        let mut len = 2;
+        while len <= size {
+            let angle = -2.0 * ::std::f64::consts::PI / len as f64;
+            for start in (0 .. size).step_by(len) {
+                for k in 0 .. len / 2 {
+                    let (w_re, w_im) = ((angle * k as f64).cos(), (angle * k as f64).sin());
+                    let (a, b) = (start + k, start + k + len / 2);
+                    let t_re = re[b] * w_re - im[b] * w_im;
+                    let t_im = re[b] * w_im + im[b] * w_re;
+                    re[b] = re[a] - t_re;
+                    im[b] = im[a] - t_im;
+                    re[a] += t_re;
+                    im[a] += t_im;
+                }
+            }
+            len <<= 1;
+        }
+        (re, im)
+    }
+
+    /// Evaluates the polynomial with these coefficients at `x` using Horner's scheme,
+    /// `content[0]` being the constant term.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let p = vector::from(&[1, 2, 3]);
+    ///
+    /// assert_eq!(p.eval_poly(2.0), 1.0 + 4.0 + 12.0);
+    /// assert_eq!(p.eval_poly(0.0), 1.0);
+    /// ```
+    pub fn eval_poly(&self, x: f64) -> f64 {
+        let mut result = 0.0;
+        for n in (0 .. self.len()).rev() {
+            result = result * x + self.content[n].to_f64();
+        }
+        result
+    }
+
+    /// Sum of the elements scaled by the given weights, `Σ wᵢxᵢ`.
+    pub fn weighted_sum(&self, weights: &Vector<T>) -> T {
+        if self.len() == weights.len() {
+            self.dot(weights)
+        } else {
+            panic!("Weights must have the same dimension as the vector!")
+        }
+    }
+
+    /// Weighted mean of the elements, `Σ wᵢxᵢ / Σ wᵢ`.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1.0, 2.0, 6.0]);
+    ///
+    /// let w = vector::from(&[3.0, 1.0, 1.0]);
+    /// assert_eq!(v.weighted_mean(&w), (3.0 + 2.0 + 6.0) / 5.0);
+    ///
+    /// let w = vector::from(&[2.0, 2.0, 2.0]);
+    /// assert_eq!(v.weighted_mean(&w), v.mean());
+    /// ```
+    pub fn weighted_mean(&self, weights: &Vector<T>) -> f64 {
+        if self.len() == weights.len() {
+            let mut sum = 0.0;
+            let mut total = 0.0;
+            for n in 0 .. self.len() {
+                sum += weights.content[n].to_f64() * self.content[n].to_f64();
+                total += weights.content[n].to_f64();
+            }
+            sum / total
+        } else {
+            panic!("Weights must have the same dimension as the vector!")
+        }
+    }
+
+    /// Cumulative maximum, each element being the largest of all elements up to and
+    /// including it.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[1, 3, 2, 5]);
+    ///
+    /// assert!(v.running_max() == vector::from(&[1, 3, 3, 5]));
+    /// assert!(v.running_min() == vector::from(&[1, 1, 1, 1]));
+    ///
+    /// let big = vector::from(&[1i64 << 53, (1i64 << 53) + 1, 1i64 << 53]);
+    /// assert!(big.running_max() == vector::from(&[1i64 << 53, (1i64 << 53) + 1, (1i64 << 53) + 1]));
+    /// ```
+    pub fn running_max(&self) -> Vector<T> {
+        self.running(|x, best| x > best)
+    }
+
+    /// Cumulative minimum, each element being the smallest of all elements up to and
+    /// including it.
+    pub fn running_min(&self) -> Vector<T> {
+        self.running(|x, best| x < best)
+    }
+
+    fn running(&self, replaces: impl Fn(T, T) -> bool) -> Vector<T> {
+        let mut pass = self.clone();
+        for n in 1 .. pass.len() {
+            if !replaces(pass.content[n], pass.content[n - 1]) {
+                pass.content[n] = pass.content[n - 1]
+            }
+        }
+        pass
+    }
+
+    /// Counts the elements falling into `bins` equally wide bins spanning the
+    /// min-max range of the vector, returning the counts and the `bins + 1` edges.
+    /// The maximum element is counted in the last bin.
+    ///
+    /// ```
+    /// use lars::vector;
+    ///
+    /// let v = vector::from(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+    /// let (counts, edges) = v.histogram(3);
+    ///
+    /// assert_eq!(counts.iter().sum::<usize>(), v.len());
+    /// assert_eq!(edges, vec![0.0, 3.0, 6.0, 9.0]);
+    /// ```
+    pub fn histogram(&self, bins: usize) -> (Vec<usize>, Vec<f64>) {
+        if bins == 0 {
+            panic!("Histogram needs at least one bin!")
+        }
+
+        let mut min = 0.0;
+        let mut max = 0.0;
+        for n in 0 .. self.len() {
+            let x = self.content[n].to_f64();
+            if n == 0 || x < min {
+                min = x
+            }
+            if n == 0 || x > max {
+                max = x
+            }
+        }
+        if min == max {
+            min -= 0.5;
+            max += 0.5;
+        }
+
+        let width = (max - min) / bins as f64;
+        let mut edges = Vec::with_capacity(bins + 1);
+        for n in 0 .. bins + 1 {
+            edges.push(min + width * n as f64);
+        }
+
+        let mut counts = vec![0; bins];
+        for n in 0 .. self.len() {
+            let bin = ((self.content[n].to_f64() - min) / width) as usize;
+            counts[if bin < bins { bin } else { bins - 1 }] += 1;
+        }
+        (counts, edges)
+    }
+
+    /// Encodes the vector as its length (`u64`) followed by the little-endian bytes
+    /// of every element.
+    ///
+    /// ```
+    /// use lars::error::ShapeError;
+    /// use lars::vector;
+    /// use lars::vector::Vector;
+    ///
+    /// let v = vector::from(&[1.5, -2.0, 42.0]);
+    /// let bytes = v.to_bytes();
+    ///
+    /// assert_eq!(bytes.len(), 8 + 3 * 8);
+    /// assert!(Vector::<f64>::from_bytes(&bytes).unwrap() == v);
+    /// assert!(Vector::<f64>::from_bytes(&bytes[.. 20]).is_err());
+    ///
+    /// let mut forged = bytes.clone();
+    /// forged[.. 8].copy_from_slice(&u64::max_value().to_le_bytes());
+    /// assert_eq!(Vector::<f64>::from_bytes(&forged).err(),
+    ///            Some(ShapeError::LengthMismatch { expected: usize::MAX, found: bytes.len() }));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> where T: LeBytes {
+        let mut bytes = Vec::with_capacity(8 + self.len() * T::byte_width());
+        bytes.extend_from_slice(&(self.len() as u64).to_le_bytes());
+        for n in 0 .. self.len() {
+            self.content[n].write_le(&mut bytes);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Vector<T>, ShapeError> where T: LeBytes {
+        if bytes.len() < 8 {
+            return Err(ShapeError::LengthMismatch {
+                expected: 8,
+                found: bytes.len(),
+            })
+        }
+
+        // The header is untrusted, so a length whose byte size overflows is reported
+        // as expecting `usize::MAX` bytes instead of wrapping around.
+        let length = u64::read_le(bytes) as usize;
+        let expected = length.checked_mul(T::byte_width()).and_then(|n| n.checked_add(8));
+        if expected != Some(bytes.len()) {
+            return Err(ShapeError::LengthMismatch {
+                expected: expected.unwrap_or(usize::MAX),
+                found: bytes.len(),
+            })
+        }
+
+        let mut v = Vector::<T> {
+            content: Vec::with_capacity(length)
+        };
+        for chunk in bytes[8 ..].chunks(T::byte_width()) {
+            v.content.push(T::read_le(chunk));
+        }
+        Ok(v)
+    }
 }
 
 pub fn from<T: Number>(elements: &[T]) -> Vector<T> {
@@ -366,6 +1462,119 @@ pub fn from<T: Number>(elements: &[T]) -> Vector<T> {
     v
 }
 
+/// Vector of `length` zeros with a single 1.0 at `index`.
+///
+/// ```
+/// use lars::vector;
+///
+/// assert!(vector::one_hot(2, 4) == vector::from(&[0.0, 0.0, 1.0, 0.0]));
+/// ```
+pub fn one_hot(index: usize, length: usize) -> Vector<f64> {
+    if index >= length {
+        panic!("Vector index {} out of bounds!", index)
+    }
+    let mut v = Vector::new(length, 0.0);
+    v.content[index] = 1.0;
+    v
+}
+
+/// Linear interpolation `a + t·(b - a)` between two equally long vectors.
+///
+/// ```
+/// use lars::vector;
+///
+/// let a = vector::from(&[0.0, 10.0]);
+/// let b = vector::from(&[4.0, 20.0]);
+///
+/// assert!(vector::lerp(&a, &b, 0.0) == a);
+/// assert!(vector::lerp(&a, &b, 1.0) == b);
+/// assert!(vector::lerp(&a, &b, 0.5) == vector::from(&[2.0, 15.0]));
+/// ```
+pub fn lerp(a: &Vector<f64>, b: &Vector<f64>, t: f64) -> Vector<f64> {
+    if a.len() != b.len() {
+        panic!("Can't interpolate vectors of different dimensions!")
+    }
+
+    let mut pass = a.clone();
+    for n in 0 .. a.len() {
+        pass.content[n] = a.content[n] + t * (b.content[n] - a.content[n]);
+    }
+    pass
+}
+
+/// Sign of the 2D cross product `a.x·b.y - a.y·b.x`: `1.0` when `b` is a
+/// counterclockwise turn from `a`, `-1.0` when clockwise and `0.0` when collinear.
+///
+/// ```
+/// use lars::vector;
+///
+/// let x = vector::from(&[1.0, 0.0]);
+/// let y = vector::from(&[0.0, 1.0]);
+///
+/// assert_eq!(vector::orientation(&x, &y), 1.0);
+/// assert_eq!(vector::orientation(&y, &x), -1.0);
+/// assert_eq!(vector::orientation(&x, &vector::from(&[-3.0, 0.0])), 0.0);
+/// ```
+pub fn orientation(a: &Vector<f64>, b: &Vector<f64>) -> f64 {
+    if a.len() != 2 || b.len() != 2 {
+        panic!("Orientation is only defined for 2D vectors!")
+    }
+
+    let cross = a[0] * b[1] - a[1] * b[0];
+    if cross == 0.0 {
+        0.0
+    } else {
+        cross.signum()
+    }
+}
+
+/// Orthonormalizes the vectors with classical Gram-Schmidt. Vectors that are (nearly)
+/// linear combinations of the previous ones are dropped, so the result spans the same
+/// space but may be shorter.
+///
+/// ```
+/// use lars::vector;
+///
+/// let basis = vector::gram_schmidt(&[
+///     vector::from(&[3.0, 1.0]),
+///     vector::from(&[2.0, 2.0]),
+///     vector::from(&[1.0, 1.0]),
+/// ]);
+///
+/// assert_eq!(basis.len(), 2);
+/// assert!(basis[0].dot(&basis[1]).abs() < 1e-12);
+/// assert!((basis[0].norm() - 1.0).abs() < 1e-12);
+/// assert!((basis[1].norm() - 1.0).abs() < 1e-12);
+/// ```
+pub fn gram_schmidt(vectors: &[Vector<f64>]) -> Vec<Vector<f64>> {
+    let mut basis: Vec<Vector<f64>> = Vec::with_capacity(vectors.len());
+    for v in vectors {
+        let mut u = v.clone();
+        for e in &basis {
+            u = u.sub_ref(&e.scale(v.dot(e)));
+        }
+
+        let norm = u.norm();
+        if norm > 1e-10 * v.norm() {
+            basis.push(u / norm);
+        }
+    }
+    basis
+}
+
+/// NaN is the only value that isn't equal to itself, so this works for any `Number`
+/// without a lossy cast to f64.
+#[allow(clippy::eq_op)]
+fn is_nan<T: Number>(x: T) -> bool {
+    x != x
+}
+
+/// Deterministic generator for the seeded sampling methods. The constant words keep
+/// the xorshift state non-zero for every seed.
+pub(crate) fn seeded_rng(seed: u64) -> rand::XorShiftRng {
+    rand::XorShiftRng::from_seed([seed as u32, (seed >> 32) as u32, 0x9E37_79B9, 0x7F4A_7C15])
+}
+
 pub fn random<T: Number + rand::Rand>(length: usize) -> Vector<T> {
     let mut d = vec![T::zero(); length];
     for x in d.iter_mut() {